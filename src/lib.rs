@@ -3,22 +3,390 @@
  * --------------------------------------------------------------------------------------------- */
 
 pub mod structures;
+pub mod units;
+pub mod series;
+pub mod writer;
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+mod test_util;
 
 use std::fs::File;
+use std::collections::{
+	BTreeMap,
+	HashMap,
+};
 use std::io::{
     Bytes,
     BufReader,
+    Read,
+    Seek,
+    SeekFrom,
 };
 use crate::structures::{
 	*,
 	bytes::*,
+	checksum::checksum,
+	vector::*,
 };
 
 /* --------------------------------------------------------------------------------------------- *
  * Define frame struct
  * --------------------------------------------------------------------------------------------- */
+// channel read in a frame: the FrAdcData, FrProcData or FrSimData structure, with its data vectors and
+// its auxiliary vectors (each list follows the FrVect next pointers), or the first FrSerData
+// structure holding the channel, with the samples decoded from the string of each FrSerData
+// structure of the frame
+#[derive(PartialEq, Debug)]
+pub struct Channel {
+	pub data: Structure,
+	pub vect: Vec<FrVect>,
+	pub aux: Vec<FrVect>,
+}
+
+impl Channel {
+
+	pub fn get_name(&self) -> String {
+		match self.data {
+			// a serial channel is named after its value in the FrSerData string
+			Structure::FrSerData(_) => self.vect[0].get_name(),
+			_ => self.data.get_name(),
+		}
+	}
+	// data of the vector chain, concatenated into one series, None if the chain is empty
+	pub fn get_values(&self) -> Option<VectData> {
+		concat_vect_chain(&self.vect)
+	}
+	pub fn get_aux_values(&self) -> Option<VectData> {
+		concat_vect_chain(&self.aux)
+	}
+}
+
+// summary of a channel read in a frame: the FrSummary structure with its moment vectors
+#[derive(PartialEq, Debug)]
+pub struct Summary {
+	pub data: FrSummary,
+	pub moments: Vec<FrVect>,
+}
+
+impl Summary {
+
+	pub fn get_name(&self) -> String {
+		self.data.get_name()
+	}
+	// moments of the chain, each vector is named after the statistic it holds
+	pub fn get_moments(&self) -> Vec<(String, VectData)> {
+		self.moments.iter().map(|x| (x.get_name(), x.get_values())).collect()
+	}
+}
+
+// table read in a frame: the FrTable structure with one vector per column
+#[derive(PartialEq, Debug)]
+pub struct Table {
+	pub data: FrTable,
+	pub columns: Vec<FrVect>,
+}
+
+impl Table {
+
+	pub fn get_name(&self) -> String {
+		self.data.get_name()
+	}
+	pub fn get_instance(&self) -> u32 {
+		self.data.get_instance()
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.data.get_next()
+	}
+	pub fn get_column_names(&self) -> Vec<String> {
+		self.data.get_column_names()
+	}
+	// values of a column, with the type of its vector
+	pub fn get_column(&self, name: &str) -> Option<VectData> {
+		let index = self.data.get_column_names().iter().position(|x| x == name)?;
+		self.columns.get(index).map(|x| x.get_values())
+	}
+}
+
+// static data: the FrStatData structure with its data vectors
+#[derive(PartialEq, Debug)]
+pub struct StatData {
+	pub data: FrStatData,
+	pub vect: Vec<FrVect>,
+}
+
+impl StatData {
+
+	pub fn get_name(&self) -> String {
+		self.data.get_name()
+	}
+	pub fn get_values(&self) -> Option<VectData> {
+		concat_vect_chain(&self.vect)
+	}
+}
+
+// structure of a class not modeled by the crate, with the vector chains it points to (one chain
+// per pointer to a FrVect, in the order of the pointers)
+#[derive(PartialEq, Debug)]
+pub struct Unknown {
+	pub data: FrUnknown,
+	pub vect: Vec<Vec<FrVect>>,
+}
+
+impl Unknown {
+
+	// name of the structure type
+	pub fn get_name(&self) -> String {
+		self.data.get_name()
+	}
+	pub fn get_class(&self) -> u16 {
+		self.data.get_class()
+	}
+}
+
+// dictionary of a file: name and elements (name and type) of each structure class, given by the
+// FrSH and FrSE structures
+pub type Dictionary = BTreeMap<u16, (String, Vec<(String, String)>)>;
+
+// structures read in a frame; a chain reaching a structure missing from the frame ends there, and
+// the pointer to that structure is listed in missing
+#[derive(PartialEq, Debug)]
+pub struct Frame {
+	pub header: FrameH,
+	pub raw_data: Option<FrRawData>,
+	pub raw_more: Vec<FrVect>,
+	pub detectors: Vec<FrDetector>,
+	pub events: Vec<FrEvent>,
+	pub sim_events: Vec<FrSimEvent>,
+	pub history: Vec<FrHistory>,
+	pub messages: Vec<FrMsg>,
+	pub summaries: Vec<Summary>,
+	pub tables: Vec<Table>,
+	pub static_data: Vec<StatData>,
+	pub channels: Vec<Channel>,
+	pub unknown: Vec<Unknown>,
+	pub missing: Vec<(u16, u32)>,
+}
+
+impl Frame {
+
+	// detectors used for the simulated data
+	pub fn get_detect_sim(&self) -> Vec<&FrDetector> {
+		get_chain(&self.detectors, self.header.get_detect_sim(),
+			FrDetector::get_instance, FrDetector::get_next).0
+	}
+	// detectors used for the processed data
+	pub fn get_detect_proc(&self) -> Vec<&FrDetector> {
+		get_chain(&self.detectors, self.header.get_detect_proc(),
+			FrDetector::get_instance, FrDetector::get_next).0
+	}
+	// events of the frame
+	pub fn get_events(&self) -> Vec<&FrEvent> {
+		get_chain(&self.events, self.header.get_event(), FrEvent::get_instance, FrEvent::get_next).0
+	}
+	// simulated events (injections) of the frame
+	pub fn get_sim_events(&self) -> Vec<&FrSimEvent> {
+		get_chain(&self.sim_events, self.header.get_sim_event(),
+			FrSimEvent::get_instance, FrSimEvent::get_next).0
+	}
+	// history of the frame
+	pub fn get_history(&self) -> Vec<&FrHistory> {
+		get_chain(&self.history, self.header.get_history(),
+			FrHistory::get_instance, FrHistory::get_next).0
+	}
+	// history of a processed channel, empty for the other channels
+	pub fn get_channel_history(&self, channel: &Channel) -> Vec<&FrHistory> {
+		match channel.data {
+			Structure::FrProcData(ref x) => get_chain(&self.history, x.get_history(),
+				FrHistory::get_instance, FrHistory::get_next).0,
+			_ => Vec::new(),
+		}
+	}
+	// summary of a channel of the frame
+	pub fn get_summary(&self, name: &str) -> Option<&Summary> {
+		self.summaries.iter().find(|x| x.get_name() == name)
+	}
+	// messages logged by the data acquisition
+	pub fn get_log_messages(&self) -> Vec<&FrMsg> {
+		match self.raw_data {
+			Some(ref raw) => get_chain(&self.messages, raw.get_log_msg(),
+				FrMsg::get_instance, FrMsg::get_next).0,
+			None => Vec::new(),
+		}
+	}
+	// tables linked from the pointer first
+	pub fn get_tables(&self, first: (u16, u32)) -> Vec<&Table> {
+		get_chain(&self.tables, first, Table::get_instance, Table::get_next).0
+	}
+	// auxiliary tables of the frame
+	pub fn get_aux_tables(&self) -> Vec<&Table> {
+		self.get_tables(self.header.get_aux_table())
+	}
+	// tables of the raw data
+	pub fn get_raw_tables(&self) -> Vec<&Table> {
+		match self.raw_data {
+			Some(ref raw) => self.get_tables(raw.get_first_table()),
+			None => Vec::new(),
+		}
+	}
+	// tables of a processed or simulated channel, empty for the other channels
+	pub fn get_channel_tables(&self, channel: &Channel) -> Vec<&Table> {
+		match channel.data {
+			Structure::FrProcData(ref x) => self.get_tables(x.get_table()),
+			Structure::FrSimData(ref x) => self.get_tables(x.get_table()),
+			Structure::FrSerData(ref x) => self.get_tables(x.get_table()),
+			_ => Vec::new(),
+		}
+	}
+	// tables of a detector
+	pub fn get_detector_tables(&self, detector: &FrDetector) -> Vec<&Table> {
+		self.get_tables(detector.get_table())
+	}
+	// static data called name of a detector, with the highest version valid at the gps time
+	pub fn get_static_data(&self, name: &str, detector: &str, gps: f64) -> Option<&StatData> {
+		self.static_data.iter()
+			.filter(|x| (x.get_name() == name) & x.data.is_valid_at(gps))
+			.filter(|x| match x.data.get_detector() {
+				(5, instance) => self.detectors.iter()
+					.any(|y| (y.get_instance() == instance) & (y.get_name() == detector)),
+				_ => false,
+			})
+			.max_by_key(|x| x.data.get_version())
+	}
+	// detector that produced a channel, found from the prefix of the channel name (e.g. H1)
+	pub fn get_detector(&self, channel_name: &str) -> Option<&FrDetector> {
+		self.detectors.iter().find(|x| channel_name.starts_with(&x.get_prefix()))
+	}
+	// pointers to the missing structures that end the chains followed by the accessors
+	fn find_missing(&self) -> Vec<(u16, u32)> {
+		let mut missing: Vec<Option<(u16, u32)>> = vec![
+			get_chain(&self.detectors, self.header.get_detect_sim(),
+				FrDetector::get_instance, FrDetector::get_next).1,
+			get_chain(&self.detectors, self.header.get_detect_proc(),
+				FrDetector::get_instance, FrDetector::get_next).1,
+			get_chain(&self.events, self.header.get_event(),
+				FrEvent::get_instance, FrEvent::get_next).1,
+			get_chain(&self.sim_events, self.header.get_sim_event(),
+				FrSimEvent::get_instance, FrSimEvent::get_next).1,
+			get_chain(&self.history, self.header.get_history(),
+				FrHistory::get_instance, FrHistory::get_next).1,
+		];
+		// tables of the frame, of the raw data, of the channels and of the detectors
+		let mut table_list: Vec<(u16, u32)> = vec![self.header.get_aux_table()];
+		if let Some(ref raw) = self.raw_data {
+			missing.push(get_chain(&self.messages, raw.get_log_msg(),
+				FrMsg::get_instance, FrMsg::get_next).1);
+			table_list.push(raw.get_first_table());
+		}
+		for channel in self.channels.iter() {
+			match channel.data {
+				Structure::FrProcData(ref x) => {
+					missing.push(get_chain(&self.history, x.get_history(),
+						FrHistory::get_instance, FrHistory::get_next).1);
+					table_list.push(x.get_table());
+				},
+				Structure::FrSimData(ref x) => table_list.push(x.get_table()),
+				Structure::FrSerData(ref x) => table_list.push(x.get_table()),
+				_ => {},
+			}
+		}
+		table_list.extend(self.detectors.iter().map(|x| x.get_table()));
+		missing.extend(table_list.into_iter()
+			.map(|x| get_chain(&self.tables, x, Table::get_instance, Table::get_next).1));
+		missing.into_iter().flatten().collect()
+	}
+}
+
+// follow a list of structures linked by their next pointer, starting from the pointer first; a
+// structure missing from the list ends the chain, the structures found before being returned with
+// the pointer to the missing structure (None for a complete chain)
+pub fn get_chain<T>(list: &[T], first: (u16, u32), instance: fn(&T) -> u32,
+	next: fn(&T) -> (u16, u32)) -> (Vec<&T>, Option<(u16, u32)>) {
+
+	let mut chain: Vec<&T> = Vec::new();
+	let mut pointer: (u16, u32) = first;
+	// a null pointer has a class equal to 0
+	while pointer.0 != 0 {
+		let current: &T = match list.iter().find(|x| instance(x) == pointer.1) {
+			Some(x) => x,
+			None => return (chain, Some(pointer)),
+		};
+		// a chain longer than the list loops back on itself
+		if chain.len() == list.len() {
+			break;
+		}
+		pointer = next(current);
+		chain.push(current);
+	}
+	(chain, None)
+}
+
+// extract the structures of a chain linked by their next pointer, starting from the pointer first:
+// links gives the next pointer of each structure of the frame, indexed by its class and instance,
+// and the structures of the chain kept in list are taken out of it; a structure missing from the
+// links ends the chain, the structures found before being returned with the pointer to the missing
+// structure (None for a complete chain)
+pub fn extract_chain(list: &mut HashMap<(u16, u32), Structure>,
+	links: &HashMap<(u16, u32), (u16, u32)>, first: (u16, u32))
+	-> (Vec<Structure>, Option<(u16, u32)>) {
 
+	let mut chain: Vec<Structure> = Vec::new();
+	let mut pointer: (u16, u32) = first;
+	// a null pointer has a class equal to 0
+	let mut steps: usize = 0;
+	while pointer.0 != 0 {
+		let next: (u16, u32) = match links.get(&pointer) {
+			Some(x) => *x,
+			None => return (chain, Some(pointer)),
+		};
+		// a chain longer than the links loops back on itself
+		if steps == links.len() {
+			break;
+		}
+		chain.extend(list.remove(&pointer));
+		pointer = next;
+		steps += 1;
+	}
+	(chain, None)
+}
 
+// concatenate the data of a list of chained vectors, None for an empty list
+pub fn concat_vect_chain(chain: &[FrVect]) -> Option<VectData> {
+
+	let mut iterator = chain.iter();
+	let mut output: VectData = iterator.next()?.get_values();
+	for vect in iterator {
+		output.append(vect.get_values());
+	}
+	Some(output)
+}
+
+// extract the vectors chained from the pointer first, following the next pointers, the vectors
+// being indexed by their instance; a vector missing from the list ends the chain, the vectors found
+// before being returned with the pointer to the missing vector (None for a complete chain)
+pub fn get_vect_chain(vect_list: &mut HashMap<u32, FrVect>, first: (u16, u32))
+	-> (Vec<FrVect>, Option<(u16, u32)>) {
+
+	let mut chain: Vec<FrVect> = Vec::new();
+	let mut pointer: (u16, u32) = first;
+	// a null pointer has a class equal to 0
+	while pointer.0 != 0 {
+		let vect: FrVect = match vect_list.remove(&pointer.1) {
+			Some(x) => x,
+			None => return (chain, Some(pointer)),
+		};
+		pointer = vect.get_next();
+		chain.push(vect);
+	}
+	(chain, None)
+}
+
+// structures of a chain, the pointer to the missing structure that ends it being added to missing
+fn keep_chain<T>(chain: (Vec<T>, Option<(u16, u32)>), missing: &mut Vec<(u16, u32)>) -> Vec<T> {
+	missing.extend(chain.1);
+	chain.0
+}
 
 /* --------------------------------------------------------------------------------------------- *
  * Frame reader functions
@@ -36,7 +404,7 @@ pub fn read_file_header(iterator: &mut Bytes<BufReader<File>>) {
 		Ok(v) => v,
 		Err(e) => panic!("Invalid UTF-8 sequence: {}", e),
 	};
-	println!("{}", s.to_string());
+	println!("{}", s);
 	let data_format: u8 = read_one_byte(iterator);
 	println!("data format: {}", data_format);
 	// library
@@ -96,77 +464,515 @@ pub fn read_frame_header(iterator: &mut Bytes<BufReader<File>>) -> (Structure, u
 }
 
 /* --------------------------------------------------------------------------------------------- */
-pub fn read_frame(iterator: &mut Bytes<BufReader<File>>, frsh: Structure,
-	name: String, gps_start: f64, gps_end: f64) -> (Vec<Structure>, Vec<Structure>) {
+// read the next structure of the file, skipping the dictionary structures (FrSH and FrSE)
+pub fn read_next_structure(iterator: &mut Bytes<BufReader<File>>) -> Structure {
+
+	let mut current = Structure::read(iterator);
+	while (current.get_class() == 1) | (current.get_class() == 2) {
+		current = Structure::read(iterator);
+	}
+	current
+}
+
+/* --------------------------------------------------------------------------------------------- */
+// read the next structure of the file, adding the dictionary structures (FrSH and FrSE) to the
+// dictionary, which describes the structures of the classes not modeled by the crate
+pub fn read_described_structure(iterator: &mut Bytes<BufReader<File>>, dictionary: &mut Dictionary)
+	-> Structure {
+
+	let mut class: u16 = 0;
+	loop {
+		match Structure::read(iterator) {
+			Structure::FrSH(x) => {
+				class = x.get_struct_class();
+				dictionary.insert(class, (x.get_name(), Vec::new()));
+			},
+			// the elements follow the header of their structure type
+			Structure::FrSE(x) => {
+				if let Some(entry) = dictionary.get_mut(&class) {
+					entry.1.push((x.get_name(), x.get_type()));
+				}
+			},
+			Structure::FrUnknown(mut x) => {
+				if let Some((name, elements)) = dictionary.get(&x.get_class()) {
+					x.set_dictionary(name, elements);
+				}
+				return Structure::FrUnknown(x);
+			},
+			x => return x,
+		}
+	}
+}
 
-	// read FrameH structure
+/* --------------------------------------------------------------------------------------------- */
+pub fn read_frame(iterator: &mut Bytes<BufReader<File>>, dictionary: &mut Dictionary,
+	header: Structure, name: String, gps_start: f64, gps_end: f64) -> Frame {
 
 	// verify that the structure is a FrameH instance
-	let struct_class = match frsh {
-		Structure::FrSH(ref x) => x.get_struct_class(),
-		_ => panic!("{:#?}", frsh),
+	let header: FrameH = match header {
+		Structure::FrameH(x) => x,
+		_ => panic!("The frame does not start with a FrameH structure: {:#?}", header),
 	};
-	assert_eq!(struct_class, 3); 
 	
 	// initialize dictionary vector
-	let mut struct_list: Vec<Structure> = Vec::new();
-	let mut vect_list: Vec<Structure> = Vec::new();
+	let mut raw_data: Option<FrRawData> = None;
+	let mut detectors: Vec<FrDetector> = Vec::new();
+	let mut events: Vec<FrEvent> = Vec::new();
+	let mut sim_events: Vec<FrSimEvent> = Vec::new();
+	let mut history: Vec<FrHistory> = Vec::new();
+	let mut messages: Vec<FrMsg> = Vec::new();
+	let mut table_list: Vec<FrTable> = Vec::new();
+	let mut stat_list: Vec<FrStatData> = Vec::new();
+	let mut unknown_list: Vec<FrUnknown> = Vec::new();
+	let mut struct_list: HashMap<(u16, u32), Structure> = HashMap::new();
+	let mut links: HashMap<(u16, u32), (u16, u32)> = HashMap::new();
+	let mut vect_list: HashMap<u32, FrVect> = HashMap::new();
 	let mut instance_list: Vec<u32> = Vec::new();
+	let mut missing: Vec<(u16, u32)> = Vec::new();
 
-	// read FrameH structure, and check the gps time
-	let mut current: Structure = read_one_structure(iterator, frsh);
-
-	// read structure header
-	let (mut frsh, mut class): (Structure, u16) = read_frame_header(iterator);
-
-		
-	let is_time_matching: bool = 
-		!((current.get_start() > gps_end) & (current.get_end() > gps_end)
-		| (current.get_start() < gps_start) & (current.get_end() < gps_start));
 	// check if the frame time match with the given time
+	let is_time_matching: bool = 
+		!((header.get_start() > gps_end) & (header.get_end() > gps_end)
+		| (header.get_start() < gps_start) & (header.get_end() < gps_start));
 
-
-	while class != 7 {
+	// read the structures until the end of frame
+	let mut current: Structure = read_described_structure(iterator, dictionary);
+	while current.get_class() != 7 {
 		
 		// check structure class
-		match class {
-			// FrAdcData or FrProcData, and add
-			4 | 11 => {
-				current = read_one_structure(iterator, frsh);
-				// add struct to the list if the names and gps times correspond to the given ones
+		match current {
+			// FrAdcData, FrProcData or FrSimData: only the next pointer is kept to follow the
+			// channel lists, and the structure itself if the names and gps times correspond to the
+			// given ones
+			Structure::FrAdcData(_) | Structure::FrProcData(_) | Structure::FrSimData(_) => {
+				let key: (u16, u32) = (current.get_class(), current.get_instance());
+				links.insert(key, current.get_next());
 				if (current.get_name() == name) & is_time_matching {
-					instance_list.push(current.get_datavector_instance());
-					struct_list.push(current);
+					// a null pointer has a class equal to 0
+					for pointer in [current.get_datavector(), current.get_aux()] {
+						if pointer.0 != 0 {
+							instance_list.push(pointer.1);
+						}
+					}
+					struct_list.insert(key, current);
 				}
-				(frsh, class) = read_frame_header(iterator);
+			},
+			// FrSerData, the channels are decoded from its string once the chain is resolved
+			Structure::FrSerData(_) => {
+				let key: (u16, u32) = (current.get_class(), current.get_instance());
+				links.insert(key, current.get_next());
+				struct_list.insert(key, current);
+			},
+			// FrSummary, kept with all its moments
+			Structure::FrSummary(ref summary) => {
+				if summary.get_moments().0 != 0 {
+					instance_list.push(summary.get_moments().1);
+				}
+				let key: (u16, u32) = (current.get_class(), current.get_instance());
+				links.insert(key, current.get_next());
+				struct_list.insert(key, current);
+			},
+			// FrTable, kept with all its columns
+			Structure::FrTable(table) => {
+				if table.get_column().0 != 0 {
+					instance_list.push(table.get_column().1);
+				}
+				table_list.push(table);
+			},
+			// FrStatData, kept with its data vectors
+			Structure::FrStatData(stat) => {
+				if stat.get_datavector().0 != 0 {
+					instance_list.push(stat.get_datavector().1);
+				}
+				stat_list.push(stat);
+			},
+			// FrRawData
+			Structure::FrRawData(raw) => {
+				if raw.get_more().0 != 0 {
+					instance_list.push(raw.get_more().1);
+				}
+				raw_data = Some(raw);
+			},
+			// FrDetector
+			Structure::FrDetector(detector) => {
+				detectors.push(detector);
+			},
+			// FrEvent
+			Structure::FrEvent(event) => {
+				events.push(event);
+			},
+			// FrSimEvent
+			Structure::FrSimEvent(event) => {
+				sim_events.push(event);
+			},
+			// FrHistory
+			Structure::FrHistory(record) => {
+				history.push(record);
+			},
+			// FrMsg
+			Structure::FrMsg(message) => {
+				messages.push(message);
 			},
 			// read FrVect
-			20 => {
-				current = read_one_structure(iterator, frsh);
-				// add FrVect in the list if the instances contains its instance
-				if instance_list.contains(&current.get_instance()) {
-					vect_list.push(current);
+			Structure::FrVect(vect) => {
+				// add FrVect in the list if the instances contains its instance, and add the
+				// next vector of the chain to the instances
+				if instance_list.contains(&vect.get_instance()) {
+					if vect.get_next().0 != 0 {
+						instance_list.push(vect.get_next().1);
+					}
+					vect_list.insert(vect.get_instance(), vect);
 				}
-				(frsh, class) = read_frame_header(iterator);
 			},
-			// error message
-			1 | 2 | 3 | 6 | 7 | 19 => {
-				println!("The structure {:#?} is not suppose to come here.", frsh);
+			// structure not modeled, kept with the vectors it points to
+			Structure::FrUnknown(unknown) => {
+				for pointer in unknown.get_pointers().unwrap_or_default() {
+					if pointer.0 == 20 {
+						instance_list.push(pointer.1);
+					}
+				}
+				unknown_list.push(unknown);
 			},
-			// Structures not recognized
+			// error message
 			_ => {
-				println!("The structure {:#?} is not recognized yet.", frsh);
+				println!("The structure {:#?} is not suppose to come here.", current);
 			},
 		};
+		current = read_described_structure(iterator, dictionary);
+	}
+
+	// follow the structure hierarchy: FrameH -> FrRawData -> FrAdcData list,
+	// FrameH -> FrProcData list and FrameH -> FrSimData list
+	let mut channel_list: Vec<Structure> = Vec::new();
+	let mut first_list: Vec<(u16, u32)> = vec![header.get_proc_data(), header.get_sim_data()];
+	if let Some(ref raw) = raw_data {
+		first_list.insert(0, raw.get_first_adc());
+	}
+	for first in first_list {
+		channel_list.append(&mut keep_chain(extract_chain(&mut struct_list, &links, first),
+			&mut missing));
+	}
+	// FrameH -> FrRawData -> FrSerData list, the serial channels are decoded from the strings
+	let ser_list: Vec<Structure> = match raw_data {
+		Some(ref raw) => keep_chain(extract_chain(&mut struct_list, &links, raw.get_first_ser()),
+			&mut missing),
+		None => Vec::new(),
+	};
+	let raw_more: Vec<FrVect> = match raw_data {
+		Some(ref raw) => keep_chain(get_vect_chain(&mut vect_list, raw.get_more()), &mut missing),
+		None => Vec::new(),
+	};
+
+	// FrameH -> FrSummary list, with the moment vectors
+	let summaries: Vec<Summary> = keep_chain(extract_chain(&mut struct_list, &links,
+		header.get_summary_data()), &mut missing)
+		.into_iter().map(|x| match x {
+		Structure::FrSummary(data) => {
+			let moments = keep_chain(get_vect_chain(&mut vect_list, data.get_moments()),
+				&mut missing);
+			Summary {
+				data,
+				moments,
+			}
+		},
+		_ => panic!("The summary list contains another structure: {:#?}", x),
+	}).collect();
+
+	// column vectors of the tables
+	let tables: Vec<Table> = table_list.into_iter().map(|data| {
+		let columns = keep_chain(get_vect_chain(&mut vect_list, data.get_column()), &mut missing);
+		Table {
+			data,
+			columns,
+		}
+	}).collect();
+
+	// data vectors of the static data
+	let static_data: Vec<StatData> = stat_list.into_iter().map(|data| {
+		let vect = keep_chain(get_vect_chain(&mut vect_list, data.get_datavector()), &mut missing);
+		StatData {
+			data,
+			vect,
+		}
+	}).collect();
+
+	// vectors of the structures not modeled
+	let unknown: Vec<Unknown> = unknown_list.into_iter().map(|data| {
+		let vect = data.get_pointers().unwrap_or_default().into_iter().filter(|x| x.0 == 20)
+			.map(|x| keep_chain(get_vect_chain(&mut vect_list, x), &mut missing)).collect();
+		Unknown {
+			data,
+			vect,
+		}
+	}).collect();
+
+	// attach the data and auxiliary vectors to the selected channels, then add the serial
+	// channel of the same name
+	let mut channels: Vec<Channel> = channel_list.into_iter().map(|data| {
+		let vect = keep_chain(get_vect_chain(&mut vect_list, data.get_datavector()), &mut missing);
+		let aux = keep_chain(get_vect_chain(&mut vect_list, data.get_aux()), &mut missing);
+		Channel {
+			data,
+			vect,
+			aux,
+		}
+	}).collect();
+	if is_time_matching {
+		// the samples are spread over the FrSerData chain, each structure holding the samples
+		// taken at its own time: they are gathered into one channel with one vector per structure
+		let mut serial: Option<Channel> = None;
+		for data in ser_list {
+			let vect: FrVect = match data {
+				Structure::FrSerData(ref ser) => match ser.get_channel(&name, header.get_start()) {
+					Some(x) => x,
+					None => continue,
+				},
+				_ => continue,
+			};
+			match serial {
+				Some(ref mut x) => x.vect.push(vect),
+				None => serial = Some(Channel {
+					data,
+					vect: vec![vect],
+					aux: Vec::new(),
+				}),
+			}
+		}
+		channels.extend(serial);
+	}
+
+	let mut frame = Frame {
+		header,
+		raw_data,
+		raw_more,
+		detectors,
+		events,
+		sim_events,
+		history,
+		messages,
+		summaries,
+		tables,
+		static_data,
+		channels,
+		unknown,
+		missing,
+	};
+	// chains followed by the accessors of the frame
+	for pointer in frame.find_missing() {
+		if !frame.missing.contains(&pointer) {
+			frame.missing.push(pointer);
+		}
+	}
+	frame
+}
+
+/* --------------------------------------------------------------------------------------------- */
+// read all the frames of a file, keeping the channels called name between gps_start and gps_end
+pub fn read_file(path: &str, name: &str, gps_start: f64, gps_end: f64) -> Vec<Frame> {
 
+	let mut iterator: Bytes<BufReader<File>> = BufReader::new(open_file(path)).bytes();
+	read_file_header(&mut iterator);
+
+	let mut dictionary: Dictionary = Dictionary::new();
+	let mut frames: Vec<Frame> = Vec::new();
+	loop {
+		match read_described_structure(&mut iterator, &mut dictionary) {
+			header @ Structure::FrameH(_) => {
+				frames.push(read_frame(&mut iterator, &mut dictionary, header, name.to_string(),
+					gps_start, gps_end));
+			},
+			Structure::FrEndOfFile(_) => break,
+			// the table of contents is not needed for a sequential read
+			_ => {},
+		}
+	}
+	frames
+}
+
+
+
+/* --------------------------------------------------------------------------------------------- */
+// list the messages of a file emitted between gps_start and gps_end, with a severity larger
+// than severity_min
+pub fn read_messages(path: &str, gps_start: f64, gps_end: f64, severity_min: u32) -> Vec<FrMsg> {
+
+	// no channel is needed, only the frame structures are kept
+	read_file(path, "", gps_start, gps_end).into_iter()
+		.flat_map(|frame| frame.messages)
+		.filter(|x| (x.get_time() >= gps_start) & (x.get_time() <= gps_end)
+			& (x.get_severity() >= severity_min))
+		.collect()
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * Random access functions
+ * --------------------------------------------------------------------------------------------- */
+fn open_file(path: &str) -> File {
+	match File::open(path) {
+		Ok(x) => x,
+		Err(e) => panic!("Can not open the file {}: {}", path, e),
+	}
+}
+
+// read the structure starting at the given position (in bytes) of the file
+pub fn read_structure_at(path: &str, position: u64) -> Structure {
+
+	let mut file = open_file(path);
+	if let Err(e) = file.seek(SeekFrom::Start(position)) {
+		panic!("Can not reach the position {} of the file {}: {}", position, path, e);
+	}
+	let mut iterator: Bytes<BufReader<File>> = BufReader::new(file).bytes();
+	read_next_structure(&mut iterator)
+}
+
+// read the table of contents, located from the end of file structure
+pub fn read_toc(path: &str) -> FrTOC {
+
+	// the seekTOC attribute is 20 bytes before the end of the file, and gives the distance
+	// between the table of contents and the end of the file
+	let mut file = open_file(path);
+	let file_size: u64 = match file.seek(SeekFrom::End(-20)) {
+		Ok(x) => x + 20,
+		Err(e) => panic!("Can not reach the end of the file {}: {}", path, e),
+	};
+	let mut iterator: Bytes<BufReader<File>> = BufReader::new(file).bytes();
+	let seek_toc: u64 = read_u64(&mut iterator);
+	if seek_toc == 0 {
+		panic!("The file {} does not have a table of contents.", path);
 	}
-	// read end of frame structure (unused)
-	let _ = read_one_structure(iterator, frsh);
 
-	(struct_list, vect_list)
+	match read_structure_at(path, file_size - seek_toc) {
+		Structure::FrTOC(x) => x,
+		x => panic!("The structure {:#?} is not a table of contents.", x),
+	}
 }
 
+// list the channels of a file, with the class of the structure holding each of them
+pub fn list_channels(path: &str) -> Vec<(String, u16)> {
+	read_toc(path).get_channels()
+}
 
+// find the events called name, between gps_start and gps_end, with an amplitude larger than
+// amplitude_min, using the index of the table of contents
+pub fn find_events(path: &str, name: &str, gps_start: f64, gps_end: f64, amplitude_min: f32)
+	-> Vec<FrEvent> {
+
+	let toc: FrTOC = read_toc(path);
+	toc.get_event_index().into_iter()
+		.filter(|(event_name, time, amplitude, _)| (event_name == name)
+			& (*time >= gps_start) & (*time <= gps_end) & (*amplitude >= amplitude_min))
+		.map(|(_, _, _, position)| match read_structure_at(path, position) {
+			Structure::FrEvent(x) => x,
+			x => panic!("The structure {:#?} is not an event.", x),
+		}).collect()
+}
+
+// find the simulated events injected between gps_start and gps_end, using the index of the table
+// of contents
+pub fn find_sim_events(path: &str, gps_start: f64, gps_end: f64) -> Vec<FrSimEvent> {
+
+	let toc: FrTOC = read_toc(path);
+	toc.get_sim_event_index().into_iter()
+		.filter(|(_, time, _, _)| (*time >= gps_start) & (*time <= gps_end))
+		.map(|(_, _, _, position)| match read_structure_at(path, position) {
+			Structure::FrSimEvent(x) => x,
+			x => panic!("The structure {:#?} is not a simulated event.", x),
+		}).collect()
+}
+
+// find the static data called name of a detector, with the highest version valid at the gps
+// time, using the index of the table of contents; the static data are returned with the pointer
+// to the missing vector that ends their chain (None for a complete chain)
+pub fn find_stat_data(path: &str, name: &str, detector: &str, gps: f64)
+	-> Option<(StatData, Option<(u16, u32)>)> {
+
+	let position: u64 = read_toc(path).find_stat(name, detector, gps)?;
+	let mut file = open_file(path);
+	if let Err(e) = file.seek(SeekFrom::Start(position)) {
+		panic!("Can not reach the position {} of the file {}: {}", position, path, e);
+	}
+	let mut iterator: Bytes<BufReader<File>> = BufReader::new(file).bytes();
+	let data: FrStatData = match read_next_structure(&mut iterator) {
+		Structure::FrStatData(x) => x,
+		x => panic!("The structure {:#?} is not a static data.", x),
+	};
+
+	// the data vectors follow the static data in the frame; a vector missing from the frame ends
+	// the chain
+	let mut vect: Vec<FrVect> = Vec::new();
+	let mut pointer: (u16, u32) = data.get_datavector();
+	while pointer.0 != 0 {
+		match read_next_structure(&mut iterator) {
+			Structure::FrVect(x) if x.get_instance() == pointer.1 => {
+				pointer = x.get_next();
+				vect.push(x);
+			},
+			Structure::FrEndOfFrame(_) | Structure::FrEndOfFile(_) => break,
+			_ => {},
+		}
+	}
+	let missing: Option<(u16, u32)> = match pointer.0 {
+		0 => None,
+		_ => Some(pointer),
+	};
+	Some((StatData {
+		data,
+		vect,
+	}, missing))
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * Check sum verification
+ * --------------------------------------------------------------------------------------------- */
+// verify the check sums of a file: the CRC of each structure whose check sum type is set, the
+// check sums of the file header and of the file held by FrEndOfFile; return the position of each
+// failing structure (0 for the file header) with a description, an empty list meaning that the
+// file is valid
+pub fn verify_file(path: &str) -> Vec<(u64, String)> {
+
+	let bytes: Vec<u8> = match std::fs::read(path) {
+		Ok(x) => x,
+		Err(e) => panic!("Can not read the file {}: {}", path, e),
+	};
+	let get_u32 = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+	let mut errors: Vec<(u64, String)> = Vec::new();
+	if bytes.len() < 40 {
+		errors.push((0, String::from("The file is too short for a frame file.")));
+		return errors;
+	}
+	// the file header is 40 bytes long and ends with the check sum type of the file
+	let file_checksum: bool = bytes[39] == 1;
+
+	let mut position: usize = 40;
+	while position + 14 <= bytes.len() {
+		let length = u64::from_le_bytes(bytes[position..position + 8].try_into().unwrap()) as usize;
+		let chk_type: u8 = bytes[position + 8];
+		let class: u8 = bytes[position + 9];
+		if (length < 18) | (position + length > bytes.len()) {
+			errors.push((position as u64, format!("Invalid length {} of a structure.", length)));
+			return errors;
+		}
+		let end: usize = position + length;
+		// FrEndOfFile ends with the check sum of the file, after its own check sum
+		let chk_sum: usize = if class == 6 { end - 8 } else { end - 4 };
+		if (chk_type == 1) & (checksum(&bytes[position..chk_sum]) != get_u32(chk_sum)) {
+			errors.push((position as u64, format!("Wrong check sum of a structure of class {}.",
+				class)));
+		}
+		if class == 6 {
+			if (chk_type == 1) & (checksum(&bytes[0..40]) != get_u32(chk_sum - 4)) {
+				errors.push((0, String::from("Wrong check sum of the file header.")));
+			}
+			if file_checksum & (checksum(&bytes[0..end - 4]) != get_u32(end - 4)) {
+				errors.push((position as u64, String::from("Wrong check sum of the file.")));
+			}
+			return errors;
+		}
+		position = end;
+	}
+	errors.push((position as u64, String::from("The end of file structure is missing.")));
+	errors
+}
 
 /* --------------------------------------------------------------------------------------------- */
 pub fn read_one_structure(iterator: &mut Bytes<BufReader<File>>, frsh: Structure) -> Structure {
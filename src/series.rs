@@ -0,0 +1,209 @@
+/* --------------------------------------------------------------------------------------------- *
+ * This file contains the definition of the decoded data series
+ * The arithmetic operations check and propagate the units of the series:
+ *	-	addition and subtraction require compatible units, the result takes the left unit
+ *	-	multiplication and division combine the units
+ * The processed data are decoded according to their type into time series, frequency series or
+ * time-frequency maps
+ * --------------------------------------------------------------------------------------------- */
+
+use std::ops::{
+	Add,
+	Sub,
+	Mul,
+	Div,
+};
+
+use crate::units::*;
+use crate::structures::vector::{
+	Axis,
+	VectData,
+};
+
+/* --------------------------------------------------------------------------------------------- *
+ * define series
+ * --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug, Clone)]
+pub struct Series {
+	pub values: Vec<f64>,
+	pub unit: Unit,
+}
+
+impl Series {
+
+	pub fn new(values: Vec<f64>, unit: Unit) -> Self {
+		Series {
+			values,
+			unit,
+		}
+	}
+	pub fn len(&self) -> usize {
+		self.values.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.values.is_empty()
+	}
+	// express the values of the series in another compatible unit
+	pub fn convert(&self, unit: Unit) -> Self {
+		let factor: f64 = self.unit.conversion_factor(&unit);
+		Series {
+			values: self.values.iter().map(|x| x * factor).collect(),
+			unit,
+		}
+	}
+
+	// apply an operation sample by sample
+	fn combine(&self, other: &Self, operation: fn(f64, f64) -> f64) -> Vec<f64> {
+		assert_eq!(self.len(), other.len(), "The series do not have the same length.");
+		self.values.iter().zip(other.values.iter()).map(|(x, y)| operation(*x, *y)).collect()
+	}
+}
+
+impl Add for Series {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		let other = other.convert(self.unit.clone());
+		Series {
+			values: self.combine(&other, |x, y| x + y),
+			unit: self.unit,
+		}
+	}
+}
+
+impl Sub for Series {
+	type Output = Self;
+	fn sub(self, other: Self) -> Self {
+		let other = other.convert(self.unit.clone());
+		Series {
+			values: self.combine(&other, |x, y| x - y),
+			unit: self.unit,
+		}
+	}
+}
+
+impl Mul for Series {
+	type Output = Self;
+	fn mul(self, other: Self) -> Self {
+		Series {
+			values: self.combine(&other, |x, y| x * y),
+			unit: self.unit * other.unit,
+		}
+	}
+}
+
+impl Div for Series {
+	type Output = Self;
+	fn div(self, other: Self) -> Self {
+		Series {
+			values: self.combine(&other, |x, y| x / y),
+			unit: self.unit / other.unit,
+		}
+	}
+}
+
+impl Mul<f64> for Series {
+	type Output = Self;
+	fn mul(self, factor: f64) -> Self {
+		Series {
+			values: self.values.iter().map(|x| x * factor).collect(),
+			unit: self.unit,
+		}
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * define processed series
+ * --------------------------------------------------------------------------------------------- */
+// kind of frequency series, given by the subType of FrProcData
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SpectrumType {
+	Unknown,
+	Dft,
+	AmplitudeSpectralDensity,
+	PowerSpectralDensity,
+	CrossSpectralDensity,
+	Coherence,
+	TransferFunction,
+}
+
+impl SpectrumType {
+
+	pub fn from_code(code: u16) -> Self {
+		match code {
+			1 => Self::Dft,
+			2 => Self::AmplitudeSpectralDensity,
+			3 => Self::PowerSpectralDensity,
+			4 => Self::CrossSpectralDensity,
+			5 => Self::Coherence,
+			6 => Self::TransferFunction,
+			_ => Self::Unknown,
+		}
+	}
+}
+
+// time series, the start of the time axis is given relatively to the start of the frame
+#[derive(PartialEq, Debug, Clone)]
+pub struct TimeSeries {
+	pub values: VectData,
+	pub unit: Unit,
+	pub time: Axis,
+}
+
+impl TimeSeries {
+
+	pub fn get_times(&self) -> Vec<f64> {
+		self.time.get_coordinates()
+	}
+}
+
+// frequency series, with the frequency range and the bandwidth of the original data
+#[derive(PartialEq, Debug, Clone)]
+pub struct FrequencySeries {
+	pub kind: SpectrumType,
+	pub values: VectData,
+	pub unit: Unit,
+	pub frequency: Axis,
+	pub f_range: f64,
+	pub bw: f64,
+}
+
+impl FrequencySeries {
+
+	pub fn get_frequencies(&self) -> Vec<f64> {
+		self.frequency.get_coordinates()
+	}
+}
+
+// time-frequency map, the values are stored time after time, each time holding all frequencies
+#[derive(PartialEq, Debug, Clone)]
+pub struct TimeFrequency {
+	pub values: VectData,
+	pub unit: Unit,
+	pub time: Axis,
+	pub frequency: Axis,
+	pub f_range: f64,
+	pub bw: f64,
+}
+
+impl TimeFrequency {
+
+	// number of times and of frequencies
+	pub fn get_shape(&self) -> (usize, usize) {
+		(self.time.n as usize, self.frequency.n as usize)
+	}
+	pub fn get_times(&self) -> Vec<f64> {
+		self.time.get_coordinates()
+	}
+	pub fn get_frequencies(&self) -> Vec<f64> {
+		self.frequency.get_coordinates()
+	}
+}
+
+// processed data decoded according to their type, the other types keep the raw values
+#[derive(PartialEq, Debug, Clone)]
+pub enum ProcSeries {
+	TimeSeries(TimeSeries),
+	FrequencySeries(FrequencySeries),
+	TimeFrequency(TimeFrequency),
+	Other(VectData),
+}
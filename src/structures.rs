@@ -5,24 +5,33 @@
  *	-	FrSE (deleted)
  *	-	FrameH
  *	-	FrAdcData
- *	-	FrDetector (TODO)
+ *	-	FrDetector
  *	-	FrEndOfFile
  *	-	FrEndOfFrame
- *	-	FrEvent (TODO)
- *	-	FrHistory (TODO)
- *	-	FrMsg (TODO)
+ *	-	FrEvent
+ *	-	FrHistory
+ *	-	FrMsg
  *	-	FrProcData
- *	-	FrRawData (TODO)
- *	-	FrSerData (TODO)
- *	-	FrSimData (TODO)
- *	-	FrSimEvent (TODO)
- *	-	FrSummary (TODO)
- *	-	FrTable (TODO)
+ *	-	FrRawData
+ *	-	FrSerData
+ *	-	FrSimData
+ *	-	FrSimEvent
+ *	-	FrStatData
+ *	-	FrSummary
+ *	-	FrTable
  *	-	FrTOC
  *	-	FrVect
+ * The structures of other classes (vendor specific structures, later additions to the format)
+ * are kept as FrUnknown, with their bytes and their dictionary entries.
  * --------------------------------------------------------------------------------------------- */
 
 pub mod bytes;
+pub mod checksum;
+pub mod compression;
+pub mod vector;
+
+#[cfg(test)]
+mod tests;
 
 use std::io::{
 	Bytes,
@@ -30,33 +39,44 @@ use std::io::{
 };
 
 use std::fs::File;
+use std::collections::BTreeMap;
+use num_complex::Complex;
 
-use crate::structures::bytes::*;
+use crate::structures::{
+	bytes::*,
+	vector::*,
+};
+use crate::units::*;
+use crate::series::*;
 
 /* --------------------------------------------------------------------------------------------- *
  * define enum of struct
  * --------------------------------------------------------------------------------------------- */
+// the table of contents is much larger than the other structures, but there is only one per file
+#[allow(clippy::large_enum_variant)]
 #[derive(PartialEq, Debug)]
 pub enum Structure {
 	FrSH(FrSH),
 	FrSE(FrSE),
 	FrameH(FrameH),
 	FrAdcData(FrAdcData),
-	//FrDetector(FrDetector),
+	FrDetector(FrDetector),
 	FrEndOfFile(FrEndOfFile),
 	FrEndOfFrame(FrEndOfFrame),
-	//FrEvent(FrEvent),
-	//FrHistory(FrHistory),
-	//FrMsg(FrMsg),
+	FrEvent(FrEvent),
+	FrHistory(FrHistory),
+	FrMsg(FrMsg),
 	FrProcData(FrProcData),
-	//FrRawData(FrRawData),
-	//FrSerData(FrSerData),
-	//FrSimData(FrSimData),
-	//FrSimEvent(FrSimEvent),
-	//FrSummary(FrSummary),
-	//FrTable(FrTable),
+	FrRawData(FrRawData),
+	FrSerData(FrSerData),
+	FrSimData(FrSimData),
+	FrSimEvent(FrSimEvent),
+	FrStatData(FrStatData),
+	FrSummary(FrSummary),
+	FrTable(FrTable),
 	FrTOC(FrTOC),
 	FrVect(FrVect),
+	FrUnknown(FrUnknown),
 }
 
 impl Structure {
@@ -86,12 +106,23 @@ impl Structure {
 			2 => Self::FrSE(FrSE::read(iterator, length, instance)),
 			3 => Self::FrameH(FrameH::read(iterator, length, instance)),
 			4 => Self::FrAdcData(FrAdcData::read(iterator, length, instance)),
+			5 => Self::FrDetector(FrDetector::read(iterator, length, instance)),
 			6 => Self::FrEndOfFile(FrEndOfFile::read(iterator, length, instance)),
 			7 => Self::FrEndOfFrame(FrEndOfFrame::read(iterator, length, instance)),
+			8 => Self::FrEvent(FrEvent::read(iterator, length, instance)),
+			9 => Self::FrHistory(FrHistory::read(iterator, length, instance)),
+			10 => Self::FrMsg(FrMsg::read(iterator, length, instance)),
 			11 => Self::FrProcData(FrProcData::read(iterator, length, instance)),
+			12 => Self::FrRawData(FrRawData::read(iterator, length, instance)),
+			13 => Self::FrSerData(FrSerData::read(iterator, length, instance)),
+			14 => Self::FrSimData(FrSimData::read(iterator, length, instance)),
+			15 => Self::FrSimEvent(FrSimEvent::read(iterator, length, instance)),
+			16 => Self::FrStatData(FrStatData::read(iterator, length, instance)),
+			17 => Self::FrSummary(FrSummary::read(iterator, length, instance)),
+			18 => Self::FrTable(FrTable::read(iterator, length, instance)),
 			19 => Self::FrTOC(FrTOC::read(iterator, length, instance)),
 			20 => Self::FrVect(FrVect::read(iterator, length, instance)),
-			_ => panic!("Structure type not recognized: {}", class),
+			_ => Self::FrUnknown(FrUnknown::read(iterator, length, instance, class as u16)),
 		};
 
 		output
@@ -118,11 +149,23 @@ impl Structure {
 			Self::FrSE(ref x) => x.class(),
 			Self::FrameH(ref x) => x.class(),
 			Self::FrAdcData(ref x) => x.class(),
+			Self::FrDetector(ref x) => x.class(),
 			Self::FrEndOfFile(ref x) => x.class(),
 			Self::FrEndOfFrame(ref x) => x.class(),
+			Self::FrEvent(ref x) => x.class(),
+			Self::FrHistory(ref x) => x.class(),
+			Self::FrMsg(ref x) => x.class(),
 			Self::FrProcData(ref x) => x.class(),
+			Self::FrRawData(ref x) => x.class(),
+			Self::FrSerData(ref x) => x.class(),
+			Self::FrSimData(ref x) => x.class(),
+			Self::FrSimEvent(ref x) => x.class(),
+			Self::FrStatData(ref x) => x.class(),
+			Self::FrSummary(ref x) => x.class(),
+			Self::FrTable(ref x) => x.class(),
 			Self::FrTOC(ref x) => x.class(),
 			Self::FrVect(ref x) => x.class(),
+			Self::FrUnknown(ref x) => x.get_class(),
 		}
 	}
 
@@ -134,11 +177,23 @@ impl Structure {
 			Self::FrSE(ref x) => x.get_instance(),
 			Self::FrameH(ref x) => x.get_instance(),
 			Self::FrAdcData(ref x) => x.get_instance(),
+			Self::FrDetector(ref x) => x.get_instance(),
 			Self::FrEndOfFile(ref x) => x.get_instance(),
 			Self::FrEndOfFrame(ref x) => x.get_instance(),
+			Self::FrEvent(ref x) => x.get_instance(),
+			Self::FrHistory(ref x) => x.get_instance(),
+			Self::FrMsg(ref x) => x.get_instance(),
 			Self::FrProcData(ref x) => x.get_instance(),
+			Self::FrRawData(ref x) => x.get_instance(),
+			Self::FrSerData(ref x) => x.get_instance(),
+			Self::FrSimData(ref x) => x.get_instance(),
+			Self::FrSimEvent(ref x) => x.get_instance(),
+			Self::FrStatData(ref x) => x.get_instance(),
+			Self::FrSummary(ref x) => x.get_instance(),
+			Self::FrTable(ref x) => x.get_instance(),
 			Self::FrTOC(ref x) => x.get_instance(),
 			Self::FrVect(ref x) => x.get_instance(),
+			Self::FrUnknown(ref x) => x.get_instance(),
 		}
 	}
 
@@ -149,11 +204,24 @@ impl Structure {
 			Self::FrSE(ref x) => x.name.clone(),
 			Self::FrameH(ref x) => x.name.clone(),
 			Self::FrAdcData(ref x) => x.name.clone(),
+			Self::FrDetector(ref x) => x.name.clone(),
 			Self::FrEndOfFile(_) => panic!("No name for FrEndOfFile structure."),
 			Self::FrEndOfFrame(_) => panic!("No name for FrEndOfFrame structure."),
+			Self::FrEvent(ref x) => x.name.clone(),
+			Self::FrHistory(ref x) => x.name.clone(),
+			Self::FrMsg(ref x) => x.alarm.clone(),
 			Self::FrProcData(ref x) => x.name.clone(),
+			Self::FrRawData(ref x) => x.name.clone(),
+			Self::FrSerData(ref x) => x.name.clone(),
+			Self::FrSimData(ref x) => x.name.clone(),
+			Self::FrSimEvent(ref x) => x.name.clone(),
+			Self::FrStatData(ref x) => x.name.clone(),
+			Self::FrSummary(ref x) => x.name.clone(),
+			Self::FrTable(ref x) => x.name.clone(),
 			Self::FrTOC(_) => panic!("No name for FrTOC structure."),
 			Self::FrVect(ref x) => x.name.clone(),
+			// name of the structure type
+			Self::FrUnknown(ref x) => x.name.clone(),
 		}
 	}
 	
@@ -173,9 +241,39 @@ impl Structure {
 		match self {
 			Self::FrProcData(ref x) => x.get_datavector_instance(),
 			Self::FrAdcData(ref x) => x.get_datavector_instance(),
+			Self::FrSimData(ref x) => x.get_datavector_instance(),
+			_ => panic!("This structure does not have a data vector."),
+		}
+	}
+	pub fn get_datavector(&self) -> (u16, u32) {
+		match self {
+			Self::FrProcData(ref x) => x.get_datavector(),
+			Self::FrAdcData(ref x) => x.get_datavector(),
+			Self::FrSimData(ref x) => x.get_datavector(),
 			_ => panic!("This structure does not have a data vector."),
 		}
 	}
+	pub fn get_aux(&self) -> (u16, u32) {
+		match self {
+			Self::FrProcData(ref x) => x.get_aux(),
+			Self::FrAdcData(ref x) => x.get_aux(),
+			// the input signal plays the role of the auxiliary vector of a simulated channel
+			Self::FrSimData(ref x) => x.get_input(),
+			_ => panic!("This structure does not have an auxiliary vector."),
+		}
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		match self {
+			Self::FrProcData(ref x) => x.get_next(),
+			Self::FrAdcData(ref x) => x.get_next(),
+			Self::FrSerData(ref x) => x.get_next(),
+			Self::FrSimData(ref x) => x.get_next(),
+			Self::FrSummary(ref x) => x.get_next(),
+			Self::FrTable(ref x) => x.get_next(),
+			Self::FrVect(ref x) => x.get_next(),
+			_ => panic!("This structure is not part of a linked list."),
+		}
+	}
 	pub fn get_compress(&self) -> u16 {
 		match self {
 			Self::FrVect(ref x) => x.get_compress(),
@@ -188,6 +286,12 @@ impl Structure {
 			_ => panic!("This structure does not have a compression level."),
 		}
 	}
+	pub fn get_values(&self) -> VectData {
+		match self {
+			Self::FrVect(ref x) => x.get_values(),
+			_ => panic!("This structure does not have a data vector."),
+		}
+	}
 }
 
 /* --------------------------------------------------------------------------------------------- *
@@ -198,6 +302,63 @@ pub trait Reader {
 	fn class(&self) -> u16;
 }
 
+pub trait Writer {
+	// name of the structure and its elements (name and type), as described in the dictionary
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>);
+	// write the content of the structure, without the common part and the check sum
+	fn write(&self, buffer: &mut Vec<u8>);
+}
+
+// write a structure with its common part (length, check sum type, class and instance) and its
+// CRC check sum; FrEndOfFile ends with the check sum of the file, which is left to the file
+// writer and not included in the returned bytes
+pub fn write_structure<T: Reader + Writer>(structure: &T, instance: u32) -> Vec<u8> {
+
+	let mut body: Vec<u8> = Vec::new();
+	structure.write(&mut body);
+	write_body(structure.class(), instance, body)
+}
+
+// write the common part, the body and the check sum of a structure
+pub fn write_body(class: u16, instance: u32, mut body: Vec<u8>) -> Vec<u8> {
+
+	// the common part is 14 bytes long and the check sum 4 bytes long
+	let mut length: u64 = (body.len() + 18) as u64;
+	if class == 6 {
+		length += 4;
+	}
+	let mut buffer: Vec<u8> = Vec::new();
+	write_u64(&mut buffer, length);
+	write_one_byte(&mut buffer, 1);
+	write_one_byte(&mut buffer, class as u8);
+	write_u32(&mut buffer, instance);
+	buffer.append(&mut body);
+	let chk_sum: u32 = checksum::checksum(&buffer);
+	write_u32(&mut buffer, chk_sum);
+	buffer
+}
+
+// split a gps time into seconds and nanoseconds
+pub fn split_gps(gps: f64) -> (u32, u32) {
+	let mut gps_sec: u32 = gps.floor() as u32;
+	let mut gps_nano: u32 = ((gps - gps.floor()) * 1e9).round() as u32;
+	if gps_nano >= 1_000_000_000 {
+		gps_sec += 1;
+		gps_nano -= 1_000_000_000;
+	}
+	(gps_sec, gps_nano)
+}
+
+// refuse to write a structure whose links to structures not written by the file writer would be
+// lost, the links being given by their element name
+fn check_links(structure: &str, name: &str, links: &[(&str, (u16, u32))]) {
+	let set: Vec<&str> = links.iter().filter(|x| x.1.0 != 0).map(|x| x.0).collect();
+	if !set.is_empty() {
+		panic!("The {} {} points to structures which are not written ({}), clear_links drops \
+			these links.", structure, name, set.join(", "));
+	}
+}
+
 #[derive(PartialEq, Debug)]
 pub struct FrSH {
 	length: u64,
@@ -217,7 +378,7 @@ impl Reader for FrSH {
 		//println!("class number: {}", class);
 		let comment: String = read_one_string(iterator);
 		//println!("comment: '{}'", comment);
-		let check_sum: u32 = read_u32(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
 
 		FrSH {
@@ -233,11 +394,35 @@ impl Reader for FrSH {
 		1
 	}
 }
+impl Writer for FrSH {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrSH", vec![("name", "STRING"), ("class", "INT_2U"), ("comment", "STRING"),
+			("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		write_u16(buffer, self.class);
+		write_one_string(buffer, &self.comment);
+	}
+}
 // getter functions
 impl FrSH {
+	// dictionary header of a structure type
+	pub fn new(name: &str, class: u16, comment: &str) -> Self {
+		FrSH {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			class,
+			comment: comment.to_string(),
+		}
+	}
 	pub fn get_struct_class(&self) -> u16 {
 		self.class
 	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
@@ -260,7 +445,7 @@ impl Reader for FrSE {
 		//println!("class: '{}'", class);
 		let comment: String = read_one_string(iterator);
 		//println!("comment: '{}'", comment);
-		let check_sum: u32 = read_u32(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
 		
 		// create structure
@@ -276,7 +461,35 @@ impl Reader for FrSE {
 		2
 	}
 }
+impl Writer for FrSE {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrSE", vec![("name", "STRING"), ("class", "STRING"), ("comment", "STRING"),
+			("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		write_one_string(buffer, &self.class);
+		write_one_string(buffer, &self.comment);
+	}
+}
 impl FrSE {
+	// dictionary element of a structure type, class being the type of the element
+	pub fn new(name: &str, class: &str, comment: &str) -> Self {
+		FrSE {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			class: class.to_string(),
+			comment: comment.to_string(),
+		}
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	// type of the element
+	pub fn get_type(&self) -> String {
+		self.class.clone()
+	}
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
@@ -290,10 +503,25 @@ pub struct FrameH {
 	name: String,
 	run: u32,
 	frame: u32,
+	data_quality: u32,
 	gps_sec: u32,
 	gps_nano: u32,
+	u_leap: u16,
 	dt: f64,
-	data: Vec<(u16, u32)>,
+	// structure pointers
+	frame_type: (u16, u32),
+	user: (u16, u32),
+	detect_sim: (u16, u32),
+	detect_proc: (u16, u32),
+	history: (u16, u32),
+	raw_data: (u16, u32),
+	proc_data: (u16, u32),
+	sim_data: (u16, u32),
+	event: (u16, u32),
+	sim_event: (u16, u32),
+	summary_data: (u16, u32),
+	aux_data: (u16, u32),
+	aux_table: (u16, u32),
 }
 impl Reader for FrameH {
 	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
@@ -315,16 +543,21 @@ impl Reader for FrameH {
 		let dt = read_f64(iterator);
 		//println!("duration: {:.9} s", dt);
 
-		// count the number of each structure instance:
-		let mut class_count: Vec<(u16, u32)> = Vec::new();
-		for _i in 0..13 {
-			let (class, instance): (u16, u32) = read_ptr(iterator);
-			//println!("class: {}, instance: {}", class, instance);
-			if class != 0 {
-				class_count.push((class, instance));
-			}
-		}
-		let check_sum: u32 = read_u32(iterator);
+		// structure pointers
+		let frame_type: (u16, u32) = read_ptr(iterator);
+		let user: (u16, u32) = read_ptr(iterator);
+		let detect_sim: (u16, u32) = read_ptr(iterator);
+		let detect_proc: (u16, u32) = read_ptr(iterator);
+		let history: (u16, u32) = read_ptr(iterator);
+		let raw_data: (u16, u32) = read_ptr(iterator);
+		let proc_data: (u16, u32) = read_ptr(iterator);
+		let sim_data: (u16, u32) = read_ptr(iterator);
+		let event: (u16, u32) = read_ptr(iterator);
+		let sim_event: (u16, u32) = read_ptr(iterator);
+		let summary_data: (u16, u32) = read_ptr(iterator);
+		let aux_data: (u16, u32) = read_ptr(iterator);
+		let aux_table: (u16, u32) = read_ptr(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
 		FrameH {
 			length,
@@ -332,10 +565,24 @@ impl Reader for FrameH {
 			name,
 			run,
 			frame,
+			data_quality,
 			gps_sec,
 			gps_nano,
+			u_leap,
 			dt,
-			data: class_count,
+			frame_type,
+			user,
+			detect_sim,
+			detect_proc,
+			history,
+			raw_data,
+			proc_data,
+			sim_data,
+			event,
+			sim_event,
+			summary_data,
+			aux_data,
+			aux_table,
 		}
 	}
 	fn class(&self) -> u16 {
@@ -343,7 +590,103 @@ impl Reader for FrameH {
 	}
 }
 
+impl Writer for FrameH {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrameH", vec![("name", "STRING"), ("run", "INT_4S"), ("frame", "INT_4U"),
+			("dataQuality", "INT_4U"), ("GTimeS", "INT_4U"), ("GTimeN", "INT_4U"),
+			("ULeapS", "INT_2U"), ("dt", "REAL_8"),
+			("type", "PTR_STRUCT(FrVect *)"), ("user", "PTR_STRUCT(FrVect *)"),
+			("detectSim", "PTR_STRUCT(FrDetector *)"), ("detectProc", "PTR_STRUCT(FrDetector *)"),
+			("history", "PTR_STRUCT(FrHistory *)"), ("rawData", "PTR_STRUCT(FrRawData *)"),
+			("procData", "PTR_STRUCT(FrProcData *)"), ("simData", "PTR_STRUCT(FrSimData *)"),
+			("event", "PTR_STRUCT(FrEvent *)"), ("simEvent", "PTR_STRUCT(FrSimEvent *)"),
+			("summaryData", "PTR_STRUCT(FrSummary *)"), ("auxData", "PTR_STRUCT(FrVect *)"),
+			("auxTable", "PTR_STRUCT(FrTable *)"), ("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		write_u32(buffer, self.run);
+		write_u32(buffer, self.frame);
+		write_u32(buffer, self.data_quality);
+		write_u32(buffer, self.gps_sec);
+		write_u32(buffer, self.gps_nano);
+		write_u16(buffer, self.u_leap);
+		write_f64(buffer, self.dt);
+		for pointer in [self.frame_type, self.user, self.detect_sim, self.detect_proc,
+			self.history, self.raw_data, self.proc_data, self.sim_data, self.event,
+			self.sim_event, self.summary_data, self.aux_data, self.aux_table] {
+			write_ptr(buffer, pointer);
+		}
+	}
+}
+
 impl FrameH {
+
+	// frame header starting at gps_start and lasting dt seconds, without any structure attached
+	pub fn new(name: &str, run: u32, frame: u32, gps_start: f64, dt: f64) -> Self {
+		let (gps_sec, gps_nano) = split_gps(gps_start);
+		FrameH {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			run,
+			frame,
+			data_quality: 0,
+			gps_sec,
+			gps_nano,
+			u_leap: 0,
+			dt,
+			frame_type: (0, 0),
+			user: (0, 0),
+			detect_sim: (0, 0),
+			detect_proc: (0, 0),
+			history: (0, 0),
+			raw_data: (0, 0),
+			proc_data: (0, 0),
+			sim_data: (0, 0),
+			event: (0, 0),
+			sim_event: (0, 0),
+			summary_data: (0, 0),
+			aux_data: (0, 0),
+			aux_table: (0, 0),
+		}
+	}
+	// link the structures written with the frame, the header must not point to other structures
+	pub(crate) fn set_links(&mut self, raw_data: (u16, u32), proc_data: (u16, u32),
+		detect_proc: (u16, u32), event: (u16, u32)) {
+		check_links("frame", &self.name, &[("type", self.frame_type), ("user", self.user),
+			("detectSim", self.detect_sim), ("history", self.history),
+			("simData", self.sim_data), ("simEvent", self.sim_event),
+			("summaryData", self.summary_data), ("auxData", self.aux_data),
+			("auxTable", self.aux_table)]);
+		self.detect_proc = detect_proc;
+		self.raw_data = raw_data;
+		self.proc_data = proc_data;
+		self.event = event;
+	}
+	// drop the links to the structures not written by the file writer: the frame type, user
+	// data, simulated detectors, history, simulated data and events, summaries, auxiliary data
+	// and tables
+	pub fn clear_links(&mut self) {
+		self.frame_type = (0, 0);
+		self.user = (0, 0);
+		self.detect_sim = (0, 0);
+		self.history = (0, 0);
+		self.sim_data = (0, 0);
+		self.sim_event = (0, 0);
+		self.summary_data = (0, 0);
+		self.aux_data = (0, 0);
+		self.aux_table = (0, 0);
+	}
+	pub fn get_run(&self) -> u32 {
+		self.run
+	}
+	pub fn get_frame(&self) -> u32 {
+		self.frame
+	}
+	pub fn get_data_quality(&self) -> u32 {
+		self.data_quality
+	}
 	
 	pub fn get_start(&self) -> f64 {
 		(self.gps_sec as f64) + (self.gps_nano as f64) * 1e-9
@@ -354,11 +697,44 @@ impl FrameH {
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_detect_sim(&self) -> (u16, u32) {
+		self.detect_sim
+	}
+	pub fn get_detect_proc(&self) -> (u16, u32) {
+		self.detect_proc
+	}
+	pub fn get_history(&self) -> (u16, u32) {
+		self.history
+	}
+	pub fn get_event(&self) -> (u16, u32) {
+		self.event
+	}
+	pub fn get_raw_data(&self) -> (u16, u32) {
+		self.raw_data
+	}
+	pub fn get_proc_data(&self) -> (u16, u32) {
+		self.proc_data
+	}
+	pub fn get_sim_data(&self) -> (u16, u32) {
+		self.sim_data
+	}
+	pub fn get_sim_event(&self) -> (u16, u32) {
+		self.sim_event
+	}
+	pub fn get_summary_data(&self) -> (u16, u32) {
+		self.summary_data
+	}
+	pub fn get_aux_table(&self) -> (u16, u32) {
+		self.aux_table
+	}
 
 }
 
 /* --------------------------------------------------------------------------------------------- */
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct FrAdcData {
 	length: u64,
 	instance: u32,
@@ -371,13 +747,15 @@ pub struct FrAdcData {
 
 	bias: f32,
 	slope: f32,
+	unit: String,
 	sample_rate: f64,
 	time_offset: f64,
 	f_shift: f64,
 	phase: f32,
 
-	valid_data: u32,
+	valid_data: u16,
 	data: (u16, u32),
+	aux: (u16, u32),
 	next: (u16, u32),
 }
 
@@ -408,7 +786,7 @@ impl Reader for FrAdcData {
 		//println!("frequency shift: {}", f_shift);
 		let phase = read_f32(iterator);
 		//println!("phase: {}", phase);
-		let valid_data = read_u32(iterator);
+		let valid_data = read_u16(iterator);
 		//println!("data valid flag: {}", valid_data);
 		// structure pointers
 		let (class_data, data): (u16, u32) = read_ptr(iterator);
@@ -417,7 +795,7 @@ impl Reader for FrAdcData {
 		//println!("class: {}, instance: {}", class_aux, aux);
 		let (class_next, next): (u16, u32) = read_ptr(iterator);
 		//println!("class: {}, instance: {}", class_next, next);
-		let check_sum: u32 = read_u32(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
 		
 		// create structure
@@ -433,6 +811,7 @@ impl Reader for FrAdcData {
 
 			bias,
 			slope,
+			unit,
 			sample_rate,
 			time_offset,
 			f_shift,
@@ -440,6 +819,7 @@ impl Reader for FrAdcData {
 
 			valid_data,
 			data: (class_data, data),
+			aux: (class_aux, aux),
 			next: (class_next, next)
 		}
 	}
@@ -447,8 +827,82 @@ impl Reader for FrAdcData {
 		4
 	}
 }
+impl Writer for FrAdcData {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrAdcData", vec![("name", "STRING"), ("comment", "STRING"),
+			("channelGroup", "INT_4U"), ("channelNumber", "INT_4U"), ("nBits", "INT_4U"),
+			("bias", "REAL_4"), ("slope", "REAL_4"), ("units", "STRING"),
+			("sampleRate", "REAL_8"), ("timeOffset", "REAL_8"), ("fShift", "REAL_8"),
+			("phase", "REAL_4"), ("dataValid", "INT_2U"),
+			("data", "PTR_STRUCT(FrVect *)"), ("aux", "PTR_STRUCT(FrVect *)"),
+			("next", "PTR_STRUCT(FrAdcData *)"), ("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		write_one_string(buffer, &self.comment);
+		write_u32(buffer, self.ch_group);
+		write_u32(buffer, self.ch_num);
+		write_u32(buffer, self.n_bits);
+		write_f32(buffer, self.bias);
+		write_f32(buffer, self.slope);
+		write_one_string(buffer, &self.unit);
+		write_f64(buffer, self.sample_rate);
+		write_f64(buffer, self.time_offset);
+		write_f64(buffer, self.f_shift);
+		write_f32(buffer, self.phase);
+		write_u16(buffer, self.valid_data);
+		write_ptr(buffer, self.data);
+		write_ptr(buffer, self.aux);
+		write_ptr(buffer, self.next);
+	}
+}
 // getter functions
 impl FrAdcData {
+
+	// ADC channel without calibration (slope 1, bias 0, counts)
+	pub fn new(name: &str, ch_group: u32, ch_num: u32, n_bits: u32, sample_rate: f64) -> Self {
+		FrAdcData {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			comment: String::new(),
+
+			ch_group,
+			ch_num,
+			n_bits,
+
+			bias: 0.,
+			slope: 1.,
+			unit: "counts".to_string(),
+			sample_rate,
+			time_offset: 0.,
+			f_shift: 0.,
+			phase: 0.,
+
+			valid_data: 0,
+			data: (0, 0),
+			aux: (0, 0),
+			next: (0, 0),
+		}
+	}
+	// calibration: the physical value is slope * counts + bias
+	pub fn set_calibration(&mut self, bias: f32, slope: f32, unit: &str) {
+		self.bias = bias;
+		self.slope = slope;
+		self.unit = unit.to_string();
+	}
+	pub fn set_comment(&mut self, comment: &str) {
+		self.comment = comment.to_string();
+	}
+	// 0 if the data are valid
+	pub fn set_valid_data(&mut self, valid_data: u16) {
+		self.valid_data = valid_data;
+	}
+	pub(crate) fn set_links(&mut self, data: (u16, u32), aux: (u16, u32), next: (u16, u32)) {
+		self.data = data;
+		self.aux = aux;
+		self.next = next;
+	}
 	
 	pub fn get_dump(&self) -> (f32, f32, f64, f64, f64, f32) {
 		(self.bias, self.slope, self.sample_rate, self.time_offset, self.f_shift, self.phase)
@@ -457,10 +911,279 @@ impl FrAdcData {
 	pub fn get_datavector_instance(&self) -> u32 {
 		self.data.1
 	}
+	pub fn get_datavector(&self) -> (u16, u32) {
+		self.data
+	}
+	pub fn get_aux(&self) -> (u16, u32) {
+		self.aux
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_sample_rate(&self) -> f64 {
+		self.sample_rate
+	}
+	pub fn get_valid_data(&self) -> u16 {
+		self.valid_data
+	}
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
+	pub fn get_f_shift(&self) -> f64 {
+		self.f_shift
+	}
+	pub fn get_phase(&self) -> f32 {
+		self.phase
+	}
+	pub fn get_bias(&self) -> f32 {
+		self.bias
+	}
+	pub fn get_slope(&self) -> f32 {
+		self.slope
+	}
+	pub fn get_unit(&self) -> Unit {
+		Unit::parse(&self.unit)
+	}
+	pub fn get_n_bits(&self) -> u32 {
+		self.n_bits
+	}
+	// smallest and largest values that the ADC can output, given its number of bits
+	pub fn get_saturation(&self) -> (i64, i64) {
+		assert!((1..=64).contains(&self.n_bits), "Invalid number of bits: {}", self.n_bits);
+		let max: i64 = ((1u64 << (self.n_bits - 1)) - 1) as i64;
+		(-max - 1, max)
+	}
+	// raw ADC counts, the counts stored as real numbers being rounded to the nearest integer;
+	// None for complex or string data
+	pub fn get_counts(&self, vect: &FrVect) -> Option<Vec<i64>> {
+		match vect.get_values() {
+			VectData::F32(x) => Some(x.iter().map(|v| v.round() as i64).collect()),
+			VectData::F64(x) => Some(x.iter().map(|v| v.round() as i64).collect()),
+			VectData::C8(_) | VectData::C16(_) | VectData::Str(_) => None,
+			x => Some(x.to_i64()),
+		}
+	}
+	// calibrated data in physical units: slope * raw + bias
+	pub fn calibrate(&self, vect: &FrVect) -> Series {
+		let values: Vec<f64> = vect.get_values().to_f64().into_iter()
+			.map(|x| (self.slope as f64) * x + (self.bias as f64)).collect();
+		Series::new(values, self.get_unit())
+	}
+	// bring the heterodyned data back to the original frequency band
+	pub fn restore_band(&self, vect: &FrVect) -> Vec<Complex<f64>> {
+		restore_heterodyne(&vect.get_values().to_complex(), 1. / self.sample_rate,
+			self.f_shift, self.phase as f64)
+	}
+
+}
+
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrDetector {
+	length: u64,
+	instance: u32,
+	name: String,
+	prefix: String,
+
+	longitude: f64,
+	latitude: f64,
+	elevation: f32,
+	arm_x_azimuth: f32,
+	arm_y_azimuth: f32,
+	arm_x_altitude: f32,
+	arm_y_altitude: f32,
+	arm_x_midpoint: f32,
+	arm_y_midpoint: f32,
+	local_time: i32,
+
+	aux: (u16, u32),
+	table: (u16, u32),
+	next: (u16, u32),
+}
+
+impl Reader for FrDetector {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		// the prefix is made of 2 characters, without length
+		let prefix: String = (0..2).map(|_| read_one_byte(iterator) as char).collect();
+		//println!("prefix: '{}'", prefix);
+		let longitude = read_f64(iterator);
+		//println!("longitude: {} rad", longitude);
+		let latitude = read_f64(iterator);
+		//println!("latitude: {} rad", latitude);
+		let elevation = read_f32(iterator);
+		//println!("elevation: {} m", elevation);
+		let arm_x_azimuth = read_f32(iterator);
+		let arm_y_azimuth = read_f32(iterator);
+		//println!("arm azimuths: {}, {} rad", arm_x_azimuth, arm_y_azimuth);
+		let arm_x_altitude = read_f32(iterator);
+		let arm_y_altitude = read_f32(iterator);
+		//println!("arm altitudes: {}, {} rad", arm_x_altitude, arm_y_altitude);
+		let arm_x_midpoint = read_f32(iterator);
+		let arm_y_midpoint = read_f32(iterator);
+		//println!("arm midpoints: {}, {} m", arm_x_midpoint, arm_y_midpoint);
+		let local_time = read_i32(iterator);
+		//println!("local time: {} s", local_time);
+		// structure pointers
+		let (class_aux, aux): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_aux, aux);
+		let (class_table, table): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_table, table);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		// create structure
+		FrDetector {
+			length,
+			instance,
+			name,
+			prefix,
+
+			longitude,
+			latitude,
+			elevation,
+			arm_x_azimuth,
+			arm_y_azimuth,
+			arm_x_altitude,
+			arm_y_altitude,
+			arm_x_midpoint,
+			arm_y_midpoint,
+			local_time,
+
+			aux: (class_aux, aux),
+			table: (class_table, table),
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		5
+	}
+}
+impl Writer for FrDetector {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrDetector", vec![("name", "STRING"), ("prefix", "CHAR[2]"),
+			("longitude", "REAL_8"), ("latitude", "REAL_8"), ("elevation", "REAL_4"),
+			("armXazimuth", "REAL_4"), ("armYazimuth", "REAL_4"),
+			("armXaltitude", "REAL_4"), ("armYaltitude", "REAL_4"),
+			("armXmidpoint", "REAL_4"), ("armYmidpoint", "REAL_4"), ("localTime", "INT_4S"),
+			("aux", "PTR_STRUCT(FrVect *)"), ("table", "PTR_STRUCT(FrTable *)"),
+			("next", "PTR_STRUCT(FrDetector *)"), ("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		// the prefix is made of 2 characters, without length
+		let mut prefix: Vec<u8> = self.prefix.bytes().collect();
+		prefix.resize(2, b' ');
+		buffer.extend_from_slice(&prefix);
+		write_f64(buffer, self.longitude);
+		write_f64(buffer, self.latitude);
+		write_f32(buffer, self.elevation);
+		write_f32(buffer, self.arm_x_azimuth);
+		write_f32(buffer, self.arm_y_azimuth);
+		write_f32(buffer, self.arm_x_altitude);
+		write_f32(buffer, self.arm_y_altitude);
+		write_f32(buffer, self.arm_x_midpoint);
+		write_f32(buffer, self.arm_y_midpoint);
+		write_i32(buffer, self.local_time);
+		write_ptr(buffer, self.aux);
+		write_ptr(buffer, self.table);
+		write_ptr(buffer, self.next);
+	}
+}
+// getter functions
+impl FrDetector {
+
+	// detector at the given longitude and latitude (radians) and elevation (meters), the
+	// prefix being the 2 characters starting its channel names
+	pub fn new(name: &str, prefix: &str, longitude: f64, latitude: f64, elevation: f32) -> Self {
+		FrDetector {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			prefix: prefix.to_string(),
+
+			longitude,
+			latitude,
+			elevation,
+			arm_x_azimuth: 0.,
+			arm_y_azimuth: 0.,
+			arm_x_altitude: 0.,
+			arm_y_altitude: 0.,
+			arm_x_midpoint: 0.,
+			arm_y_midpoint: 0.,
+			local_time: 0,
+
+			aux: (0, 0),
+			table: (0, 0),
+			next: (0, 0),
+		}
+	}
+	// azimuths and altitudes in radians, midpoints in meters, for the x and y arms
+	pub fn set_arms(&mut self, azimuth: (f32, f32), altitude: (f32, f32), midpoint: (f32, f32)) {
+		(self.arm_x_azimuth, self.arm_y_azimuth) = azimuth;
+		(self.arm_x_altitude, self.arm_y_altitude) = altitude;
+		(self.arm_x_midpoint, self.arm_y_midpoint) = midpoint;
+	}
+	pub fn set_local_time(&mut self, local_time: i32) {
+		self.local_time = local_time;
+	}
+	// link the detector list, the detector must not point to auxiliary vectors or tables
+	pub(crate) fn set_links(&mut self, next: (u16, u32)) {
+		check_links("detector", &self.name, &[("aux", self.aux), ("table", self.table)]);
+		self.next = next;
+	}
+	// drop the links to the auxiliary vectors and tables, which are not written by the file
+	// writer
+	pub fn clear_links(&mut self) {
+		self.aux = (0, 0);
+		self.table = (0, 0);
+	}
 
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_prefix(&self) -> String {
+		self.prefix.clone()
+	}
+	// longitude and latitude in radians, elevation in meters
+	pub fn get_position(&self) -> (f64, f64, f32) {
+		(self.longitude, self.latitude, self.elevation)
+	}
+	// azimuths of the x and y arms in radians
+	pub fn get_arm_azimuth(&self) -> (f32, f32) {
+		(self.arm_x_azimuth, self.arm_y_azimuth)
+	}
+	// altitudes of the x and y arms in radians
+	pub fn get_arm_altitude(&self) -> (f32, f32) {
+		(self.arm_x_altitude, self.arm_y_altitude)
+	}
+	// distances between the vertex and the middle of the x and y arms in meters
+	pub fn get_arm_midpoint(&self) -> (f32, f32) {
+		(self.arm_x_midpoint, self.arm_y_midpoint)
+	}
+	// local time offset from UTC in seconds
+	pub fn get_local_time(&self) -> i32 {
+		self.local_time
+	}
+	pub fn get_aux(&self) -> (u16, u32) {
+		self.aux
+	}
+	pub fn get_table(&self) -> (u16, u32) {
+		self.table
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
 }
 
 /* --------------------------------------------------------------------------------------------- */
@@ -482,13 +1205,13 @@ impl Reader for FrEndOfFile {
 		//println!("total bytes: {}", n_bytes);
 		let seek_toc = read_u64(iterator);
 		//println!("# bytes to TOC: {} s", seek_toc);
-		let check_sum_toc = read_u32(iterator);
-		//println!("check sum TOC: {} ns", check_sum_toc);
-		let check_sum_header = read_u32(iterator);
+		// version 8 of the format (LIGO-T970130, FrEndOfFile) has no TOC check sum: seekTOC is
+		// followed by chkSumFrHeader, chkSum and chkSumFile, the structure being 46 bytes long
+		let _check_sum_header = read_u32(iterator);
 		//println!("check sum header: {} ns", check_sum_header);
-		let check_sum: u32 = read_u32(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
-		let check_sum_file: u32 = read_u32(iterator);
+		let _check_sum_file: u32 = read_u32(iterator);
 		//println!("check sum file: {}", check_sum_file);
 
 		FrEndOfFile {
@@ -504,10 +1227,47 @@ impl Reader for FrEndOfFile {
 		6
 	}
 }
+impl Writer for FrEndOfFile {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrEndOfFile", vec![("nFrames", "INT_4U"), ("nBytes", "INT_8U"),
+			("seekTOC", "INT_8U"), ("chkSumFrHeader", "INT_4U"), ("chkSum", "INT_4U"),
+			("chkSumFile", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_u32(buffer, self.n_frames);
+		write_u64(buffer, self.n_bytes);
+		write_u64(buffer, self.seek_toc);
+		// check sum of the file header
+		let mut header: Vec<u8> = Vec::new();
+		write_file_header(&mut header);
+		write_u32(buffer, checksum::checksum(&header));
+		// the check sum of the structure is added by write_structure, and the check sum of the
+		// file by the file writer
+	}
+}
 impl FrEndOfFile {
+	// n_bytes is the size of the whole file, this structure included
+	pub fn new(n_frames: u32, n_bytes: u64, seek_toc: u64) -> Self {
+		FrEndOfFile {
+			length: 0,
+			instance: 0,
+			n_frames,
+			n_bytes,
+			seek_toc,
+		}
+	}
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
+	pub fn get_n_frames(&self) -> u32 {
+		self.n_frames
+	}
+	pub fn get_n_bytes(&self) -> u64 {
+		self.n_bytes
+	}
+	pub fn get_seek_toc(&self) -> u64 {
+		self.seek_toc
+	}
 
 }
 /* --------------------------------------------------------------------------------------------- */
@@ -532,7 +1292,7 @@ impl Reader for FrEndOfFrame {
 		//println!("frame start: {} s", gps_sec);
 		let gps_nano = read_u32(iterator);
 		//println!("residual: {} ns", gps_nano);
-		let check_sum: u32 = read_u32(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
 
 		FrEndOfFrame {
@@ -548,156 +1308,1474 @@ impl Reader for FrEndOfFrame {
 		7
 	}
 }
+impl Writer for FrEndOfFrame {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrEndOfFrame", vec![("run", "INT_4S"), ("frame", "INT_4U"), ("GTimeS", "INT_4U"),
+			("GTimeN", "INT_4U"), ("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_u32(buffer, self.run);
+		write_u32(buffer, self.frame);
+		write_u32(buffer, self.gps_sec);
+		write_u32(buffer, self.gps_nano);
+	}
+}
 impl FrEndOfFrame {
+	// end of the frame described by the header
+	pub fn new(header: &FrameH) -> Self {
+		FrEndOfFrame {
+			length: 0,
+			instance: 0,
+			run: header.run,
+			frame: header.frame,
+			gps_sec: header.gps_sec,
+			gps_nano: header.gps_nano,
+		}
+	}
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
 }
 /* --------------------------------------------------------------------------------------------- */
 #[derive(PartialEq, Debug)]
-pub struct FrProcData {
+pub struct FrEvent {
 	length: u64,
 	instance: u32,
 	name: String,
 	comment: String,
+	inputs: String,
 
-	object_type: u16,
-	sub_type: u16,
-	time_offset: f64,
-	time_range: f64,
-	f_shift: f64,
-	phase: f32,
-	f_range: f64,
-	bw: f64,
+	gps_sec: u32,
+	gps_nano: u32,
+	time_before: f32,
+	time_after: f32,
+	event_status: u32,
+	amplitude: f32,
+	probability: f32,
+	statistics: String,
+	param: Vec<(String, f64)>,
 
 	data: (u16, u32),
-	next: (u16, u32)
+	table: (u16, u32),
+	next: (u16, u32),
 }
-impl Reader for FrProcData {
+
+impl Reader for FrEvent {
 	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
 
 		let name: String = read_one_string(iterator);
 		//println!("name: '{}'", name);
 		let comment: String = read_one_string(iterator);
 		//println!("comment: '{}'", comment);
-		let object_type: u16 = read_u16(iterator);
-		//println!("type 1: {}", object_type);
-		let sub_type: u16 = read_u16(iterator);
-		//println!("type 2: {}", sub_type);
-		let time_offset = read_f64(iterator);
-		//println!("start time: {} s", time_offset);
-		let time_range = read_f64(iterator);
-		//println!("duration: {} s", time_range);
-		let f_shift = read_f64(iterator);
-		//println!("frequency shift: {} Hz", f_shift);
-		let phase = read_f32(iterator);
-		//println!("phase: {} rad", phase);
-		let f_range = read_f64(iterator);
-		//println!("frequency range: {} Hz", f_range);
-		let bw = read_f64(iterator);
-		//println!("bandwidth: {} Hz", bw);
-		
-		// auxiliary parameters
-		let n_aux: u16 = read_u16(iterator);
-		//println!("# of auxiliary parameters: {}", n_aux);
-		let mut param: Vec<f64> = Vec::new();
-		let mut param_name: Vec<String> = Vec::new();
-		for _i in 0..n_aux {
-			param.push(read_f64(iterator));
+		let inputs: String = read_one_string(iterator);
+		//println!("inputs: '{}'", inputs);
+		let gps_sec = read_u32(iterator);
+		//println!("event time: {} s", gps_sec);
+		let gps_nano = read_u32(iterator);
+		//println!("residual: {} ns", gps_nano);
+		let time_before = read_f32(iterator);
+		//println!("duration before: {} s", time_before);
+		let time_after = read_f32(iterator);
+		//println!("duration after: {} s", time_after);
+		let event_status = read_u32(iterator);
+		//println!("status: {}", event_status);
+		let amplitude = read_f32(iterator);
+		//println!("amplitude: {}", amplitude);
+		let probability = read_f32(iterator);
+		//println!("probability: {}", probability);
+		let statistics: String = read_one_string(iterator);
+		//println!("statistics: '{}'", statistics);
+
+		// parameters
+		let n_param: u16 = read_u16(iterator);
+		//println!("# of parameters: {}", n_param);
+		let mut values: Vec<f64> = Vec::new();
+		for _i in 0..n_param {
+			values.push(read_f64(iterator));
 		}
-		for _i in 0..n_aux {
-			param_name.push(read_one_string(iterator));
+		let mut param: Vec<(String, f64)> = Vec::new();
+		for value in values {
+			param.push((read_one_string(iterator), value));
 		}
-		//println!("names: {:#?}", param_name);
-		//println!("values: {:#?}", param);	
+		//println!("parameters: {:#?}", param);
 		// structure pointers
 		let (class_data, data): (u16, u32) = read_ptr(iterator);
 		//println!("class: {}, instance: {}", class_data, data);
-		let (class_aux, aux): (u16, u32) = read_ptr(iterator);
-		//println!("class: {}, instance: {}", class_aux, aux);
 		let (class_table, table): (u16, u32) = read_ptr(iterator);
 		//println!("class: {}, instance: {}", class_table, table);
-		let (class_history, history): (u16, u32) = read_ptr(iterator);
-		//println!("class: {}, instance: {}", class_history, history);
 		let (class_next, next): (u16, u32) = read_ptr(iterator);
 		//println!("class: {}, instance: {}", class_next, next);
-
-		let check_sum: u32 = read_u32(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
+
 		// create structure
-		FrProcData {
+		FrEvent {
 			length,
 			instance,
 			name,
 			comment,
+			inputs,
 
-			object_type,
-			sub_type,
-			time_offset,
-			time_range,
-			f_shift,
-			phase,
-			f_range,
-			bw,
+			gps_sec,
+			gps_nano,
+			time_before,
+			time_after,
+			event_status,
+			amplitude,
+			probability,
+			statistics,
+			param,
+
+			data: (class_data, data),
+			table: (class_table, table),
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		8
+	}
+}
+impl Writer for FrEvent {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrEvent", vec![("name", "STRING"), ("comment", "STRING"), ("inputs", "STRING"),
+			("GTimeS", "INT_4U"), ("GTimeN", "INT_4U"), ("timeBefore", "REAL_4"),
+			("timeAfter", "REAL_4"), ("eventStatus", "INT_4U"), ("amplitude", "REAL_4"),
+			("probability", "REAL_4"), ("statistics", "STRING"), ("nParam", "INT_2U"),
+			("parameters", "REAL_8[nParam]"), ("parameterNames", "STRING[nParam]"),
+			("data", "PTR_STRUCT(FrVect *)"), ("table", "PTR_STRUCT(FrTable *)"),
+			("next", "PTR_STRUCT(FrEvent *)"), ("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		write_one_string(buffer, &self.comment);
+		write_one_string(buffer, &self.inputs);
+		write_u32(buffer, self.gps_sec);
+		write_u32(buffer, self.gps_nano);
+		write_f32(buffer, self.time_before);
+		write_f32(buffer, self.time_after);
+		write_u32(buffer, self.event_status);
+		write_f32(buffer, self.amplitude);
+		write_f32(buffer, self.probability);
+		write_one_string(buffer, &self.statistics);
+		write_u16(buffer, self.param.len() as u16);
+		for (_, value) in self.param.iter() {
+			write_f64(buffer, *value);
+		}
+		for (name, _) in self.param.iter() {
+			write_one_string(buffer, name);
+		}
+		write_ptr(buffer, self.data);
+		write_ptr(buffer, self.table);
+		write_ptr(buffer, self.next);
+	}
+}
+// getter functions
+impl FrEvent {
+
+	// event at the gps time, with the given amplitude
+	pub fn new(name: &str, gps: f64, amplitude: f32) -> Self {
+		let (gps_sec, gps_nano) = split_gps(gps);
+		FrEvent {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			comment: String::new(),
+			inputs: String::new(),
+
+			gps_sec,
+			gps_nano,
+			time_before: 0.,
+			time_after: 0.,
+			event_status: 0,
+			amplitude,
+			probability: 0.,
+			statistics: String::new(),
+			param: Vec::new(),
+
+			data: (0, 0),
+			table: (0, 0),
+			next: (0, 0),
+		}
+	}
+	pub fn set_comment(&mut self, comment: &str) {
+		self.comment = comment.to_string();
+	}
+	pub fn set_duration(&mut self, time_before: f32, time_after: f32) {
+		self.time_before = time_before;
+		self.time_after = time_after;
+	}
+	pub fn set_param(&mut self, name: &str, value: f64) {
+		self.param.push((name.to_string(), value));
+	}
+	// link the event list, the event must not point to data vectors or tables
+	pub(crate) fn set_links(&mut self, next: (u16, u32)) {
+		check_links("event", &self.name, &[("data", self.data), ("table", self.table)]);
+		self.next = next;
+	}
+	// drop the links to the data vectors and tables, which are not written by the file writer
+	pub fn clear_links(&mut self) {
+		self.data = (0, 0);
+		self.table = (0, 0);
+	}
+
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_comment(&self) -> String {
+		self.comment.clone()
+	}
+	pub fn get_inputs(&self) -> String {
+		self.inputs.clone()
+	}
+	pub fn get_time(&self) -> f64 {
+		(self.gps_sec as f64) + (self.gps_nano as f64) * 1e-9
+	}
+	// durations of the event before and after its time
+	pub fn get_duration(&self) -> (f32, f32) {
+		(self.time_before, self.time_after)
+	}
+	pub fn get_event_status(&self) -> u32 {
+		self.event_status
+	}
+	pub fn get_amplitude(&self) -> f32 {
+		self.amplitude
+	}
+	pub fn get_probability(&self) -> f32 {
+		self.probability
+	}
+	pub fn get_statistics(&self) -> String {
+		self.statistics.clone()
+	}
+	// parameter names with their values
+	pub fn get_param(&self) -> Vec<(String, f64)> {
+		self.param.clone()
+	}
+	pub fn get_data(&self) -> (u16, u32) {
+		self.data
+	}
+	pub fn get_table(&self) -> (u16, u32) {
+		self.table
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrSimEvent {
+	length: u64,
+	instance: u32,
+	name: String,
+	comment: String,
+	inputs: String,
+
+	gps_sec: u32,
+	gps_nano: u32,
+	time_before: f32,
+	time_after: f32,
+	amplitude: f32,
+	param: Vec<(String, f64)>,
+
+	data: (u16, u32),
+	table: (u16, u32),
+	next: (u16, u32),
+}
+
+impl Reader for FrSimEvent {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		let comment: String = read_one_string(iterator);
+		//println!("comment: '{}'", comment);
+		let inputs: String = read_one_string(iterator);
+		//println!("inputs: '{}'", inputs);
+		let gps_sec = read_u32(iterator);
+		//println!("injection time: {} s", gps_sec);
+		let gps_nano = read_u32(iterator);
+		//println!("residual: {} ns", gps_nano);
+		let time_before = read_f32(iterator);
+		//println!("duration before: {} s", time_before);
+		let time_after = read_f32(iterator);
+		//println!("duration after: {} s", time_after);
+		let amplitude = read_f32(iterator);
+		//println!("amplitude: {}", amplitude);
+
+		// parameters
+		let n_param: u16 = read_u16(iterator);
+		//println!("# of parameters: {}", n_param);
+		let mut values: Vec<f64> = Vec::new();
+		for _i in 0..n_param {
+			values.push(read_f64(iterator));
+		}
+		let mut param: Vec<(String, f64)> = Vec::new();
+		for value in values {
+			param.push((read_one_string(iterator), value));
+		}
+		//println!("parameters: {:#?}", param);
+		// structure pointers
+		let (class_data, data): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_data, data);
+		let (class_table, table): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_table, table);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		// create structure
+		FrSimEvent {
+			length,
+			instance,
+			name,
+			comment,
+			inputs,
+
+			gps_sec,
+			gps_nano,
+			time_before,
+			time_after,
+			amplitude,
+			param,
+
+			data: (class_data, data),
+			table: (class_table, table),
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		15
+	}
+}
+// getter functions
+impl FrSimEvent {
+
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_comment(&self) -> String {
+		self.comment.clone()
+	}
+	pub fn get_inputs(&self) -> String {
+		self.inputs.clone()
+	}
+	pub fn get_time(&self) -> f64 {
+		(self.gps_sec as f64) + (self.gps_nano as f64) * 1e-9
+	}
+	// durations of the injection before and after its time
+	pub fn get_duration(&self) -> (f32, f32) {
+		(self.time_before, self.time_after)
+	}
+	pub fn get_amplitude(&self) -> f32 {
+		self.amplitude
+	}
+	// injection parameter names with their values
+	pub fn get_param(&self) -> Vec<(String, f64)> {
+		self.param.clone()
+	}
+	pub fn get_data(&self) -> (u16, u32) {
+		self.data
+	}
+	pub fn get_table(&self) -> (u16, u32) {
+		self.table
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrHistory {
+	length: u64,
+	instance: u32,
+	name: String,
+	time: u32,
+	comment: String,
+	next: (u16, u32),
+}
+
+impl Reader for FrHistory {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		let time = read_u32(iterator);
+		//println!("time: {} s", time);
+		let comment: String = read_one_string(iterator);
+		//println!("comment: '{}'", comment);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		FrHistory {
+			length,
+			instance,
+			name,
+			time,
+			comment,
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		9
+	}
+}
+// getter functions
+impl FrHistory {
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	// gps time of the history record
+	pub fn get_time(&self) -> u32 {
+		self.time
+	}
+	pub fn get_comment(&self) -> String {
+		self.comment.clone()
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrMsg {
+	length: u64,
+	instance: u32,
+	alarm: String,
+	message: String,
+	severity: u32,
+	gps_sec: u32,
+	gps_nano: u32,
+	next: (u16, u32),
+}
+
+impl Reader for FrMsg {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let alarm: String = read_one_string(iterator);
+		//println!("alarm: '{}'", alarm);
+		let message: String = read_one_string(iterator);
+		//println!("message: '{}'", message);
+		let severity = read_u32(iterator);
+		//println!("severity: {}", severity);
+		let gps_sec = read_u32(iterator);
+		//println!("message time: {} s", gps_sec);
+		let gps_nano = read_u32(iterator);
+		//println!("residual: {} ns", gps_nano);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		FrMsg {
+			length,
+			instance,
+			alarm,
+			message,
+			severity,
+			gps_sec,
+			gps_nano,
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		10
+	}
+}
+// getter functions
+impl FrMsg {
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_alarm(&self) -> String {
+		self.alarm.clone()
+	}
+	pub fn get_message(&self) -> String {
+		self.message.clone()
+	}
+	pub fn get_severity(&self) -> u32 {
+		self.severity
+	}
+	pub fn get_time(&self) -> f64 {
+		(self.gps_sec as f64) + (self.gps_nano as f64) * 1e-9
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrProcData {
+	length: u64,
+	instance: u32,
+	name: String,
+	comment: String,
+
+	object_type: u16,
+	sub_type: u16,
+	time_offset: f64,
+	time_range: f64,
+	f_shift: f64,
+	phase: f32,
+	f_range: f64,
+	bw: f64,
+	aux_param: Vec<(String, f64)>,
+
+	data: (u16, u32),
+	aux: (u16, u32),
+	table: (u16, u32),
+	history: (u16, u32),
+	next: (u16, u32)
+}
+impl Reader for FrProcData {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		let comment: String = read_one_string(iterator);
+		//println!("comment: '{}'", comment);
+		let object_type: u16 = read_u16(iterator);
+		//println!("type 1: {}", object_type);
+		let sub_type: u16 = read_u16(iterator);
+		//println!("type 2: {}", sub_type);
+		let time_offset = read_f64(iterator);
+		//println!("start time: {} s", time_offset);
+		let time_range = read_f64(iterator);
+		//println!("duration: {} s", time_range);
+		let f_shift = read_f64(iterator);
+		//println!("frequency shift: {} Hz", f_shift);
+		let phase = read_f32(iterator);
+		//println!("phase: {} rad", phase);
+		let f_range = read_f64(iterator);
+		//println!("frequency range: {} Hz", f_range);
+		let bw = read_f64(iterator);
+		//println!("bandwidth: {} Hz", bw);
+		
+		// auxiliary parameters
+		let n_aux: u16 = read_u16(iterator);
+		//println!("# of auxiliary parameters: {}", n_aux);
+		let mut param: Vec<f64> = Vec::new();
+		let mut param_name: Vec<String> = Vec::new();
+		for _i in 0..n_aux {
+			param.push(read_f64(iterator));
+		}
+		for _i in 0..n_aux {
+			param_name.push(read_one_string(iterator));
+		}
+		//println!("names: {:#?}", param_name);
+		//println!("values: {:#?}", param);	
+		// the parameters are kept in the order of the file, a name may be repeated
+		let aux_param: Vec<(String, f64)> = param_name.into_iter().zip(param).collect();
+		// structure pointers
+		let (class_data, data): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_data, data);
+		let (class_aux, aux): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_aux, aux);
+		let (class_table, table): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_table, table);
+		let (class_history, history): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_history, history);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+		// create structure
+		FrProcData {
+			length,
+			instance,
+			name,
+			comment,
+
+			object_type,
+			sub_type,
+			time_offset,
+			time_range,
+			f_shift,
+			phase,
+			f_range,
+			bw,
+			aux_param,
+
+			data: (class_data, data),
+			aux: (class_aux, aux),
+			table: (class_table, table),
+			history: (class_history, history),
+			next: (class_next, next)
+		}
+	}
+	fn class(&self) -> u16 {
+		11
+	}
+}
+impl Writer for FrProcData {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrProcData", vec![("name", "STRING"), ("comment", "STRING"), ("type", "INT_2U"),
+			("subType", "INT_2U"), ("timeOffset", "REAL_8"), ("tRange", "REAL_8"),
+			("fShift", "REAL_8"), ("phase", "REAL_4"), ("fRange", "REAL_8"), ("BW", "REAL_8"),
+			("nAuxParam", "INT_2U"), ("auxParam", "REAL_8[nAuxParam]"),
+			("auxParamNames", "STRING[nAuxParam]"),
+			("data", "PTR_STRUCT(FrVect *)"), ("aux", "PTR_STRUCT(FrVect *)"),
+			("table", "PTR_STRUCT(FrTable *)"), ("history", "PTR_STRUCT(FrHistory *)"),
+			("next", "PTR_STRUCT(FrProcData *)"), ("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		write_one_string(buffer, &self.comment);
+		write_u16(buffer, self.object_type);
+		write_u16(buffer, self.sub_type);
+		write_f64(buffer, self.time_offset);
+		write_f64(buffer, self.time_range);
+		write_f64(buffer, self.f_shift);
+		write_f32(buffer, self.phase);
+		write_f64(buffer, self.f_range);
+		write_f64(buffer, self.bw);
+		write_u16(buffer, self.aux_param.len() as u16);
+		for (_, value) in self.aux_param.iter() {
+			write_f64(buffer, *value);
+		}
+		for (name, _) in self.aux_param.iter() {
+			write_one_string(buffer, name);
+		}
+		write_ptr(buffer, self.data);
+		write_ptr(buffer, self.aux);
+		write_ptr(buffer, self.table);
+		write_ptr(buffer, self.history);
+		write_ptr(buffer, self.next);
+	}
+}
+// getter functions
+impl FrProcData {
+
+	// processed data of the given type (1 time series, 2 frequency series, 4 time-frequency)
+	// covering time_range seconds
+	pub fn new(name: &str, object_type: u16, sub_type: u16, time_range: f64) -> Self {
+		FrProcData {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			comment: String::new(),
+
+			object_type,
+			sub_type,
+			time_offset: 0.,
+			time_range,
+			f_shift: 0.,
+			phase: 0.,
+			f_range: 0.,
+			bw: 0.,
+			aux_param: Vec::new(),
+
+			data: (0, 0),
+			aux: (0, 0),
+			table: (0, 0),
+			history: (0, 0),
+			next: (0, 0),
+		}
+	}
+	pub fn set_comment(&mut self, comment: &str) {
+		self.comment = comment.to_string();
+	}
+	pub fn set_time_offset(&mut self, time_offset: f64) {
+		self.time_offset = time_offset;
+	}
+	// frequency range and bandwidth of the frequency series
+	pub fn set_frequency(&mut self, f_range: f64, bw: f64) {
+		self.f_range = f_range;
+		self.bw = bw;
+	}
+	pub fn set_heterodyne(&mut self, f_shift: f64, phase: f32) {
+		self.f_shift = f_shift;
+		self.phase = phase;
+	}
+	// set the first auxiliary parameter called name, or add it after the others
+	pub fn set_aux_param(&mut self, name: &str, value: f64) {
+		match self.aux_param.iter_mut().find(|x| x.0 == name) {
+			Some(x) => x.1 = value,
+			None => self.aux_param.push((name.to_string(), value)),
+		}
+	}
+	// link the vectors written with the data, the channel must not point to tables or history
+	pub(crate) fn set_links(&mut self, data: (u16, u32), aux: (u16, u32), next: (u16, u32)) {
+		check_links("channel", &self.name, &[("table", self.table), ("history", self.history)]);
+		self.data = data;
+		self.aux = aux;
+		self.next = next;
+	}
+	// drop the links to the tables and history, which are not written by the file writer
+	pub fn clear_links(&mut self) {
+		self.table = (0, 0);
+		self.history = (0, 0);
+	}
+	
+	pub fn get_dump(&self) -> (u16, u16, f64, f64, f64, f32, f64, f64) {
+		(self.object_type, self.sub_type, self.time_offset, self.time_range, self.f_shift,
+		self.phase, self.f_range, self.bw)
+	}
+
+	pub fn get_datavector_instance(&self) -> u32 {
+		self.data.1
+	}
+	pub fn get_datavector(&self) -> (u16, u32) {
+		self.data
+	}
+	pub fn get_aux(&self) -> (u16, u32) {
+		self.aux
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+
+	pub fn get_table(&self) -> (u16, u32) {
+		self.table
+	}
+
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+
+	pub fn get_comment(&self) -> String {
+		self.comment.clone()
+	}
+
+	// auxiliary parameters describing how the data were produced, with their names, in the
+	// order of the file
+	pub fn get_aux_param(&self) -> Vec<(String, f64)> {
+		self.aux_param.clone()
+	}
+
+	// value of the first auxiliary parameter called name
+	pub fn get_aux_param_value(&self, name: &str) -> Option<f64> {
+		self.aux_param.iter().find(|x| x.0 == name).map(|x| x.1)
+	}
+
+	pub fn get_history(&self) -> (u16, u32) {
+		self.history
+	}
+
+	pub fn get_f_shift(&self) -> f64 {
+		self.f_shift
+	}
+
+	pub fn get_phase(&self) -> f32 {
+		self.phase
+	}
+
+	// decode the data vector according to the type of the processed data: 1 for a time series,
+	// 2 for a frequency series (the sub type gives the kind of spectrum) and 4 for a
+	// time-frequency map
+	pub fn decode(&self, vect: &FrVect) -> ProcSeries {
+		let axes: Vec<Axis> = vect.get_axes();
+		let values: VectData = vect.get_values();
+		let unit: Unit = vect.get_unit_y();
+		match (self.object_type, axes.len()) {
+			(1, 1) => {
+				let mut time: Axis = axes[0].clone();
+				time.start += self.time_offset;
+				ProcSeries::TimeSeries(TimeSeries {
+					values,
+					unit,
+					time,
+				})
+			},
+			(2, 1) => ProcSeries::FrequencySeries(FrequencySeries {
+				kind: SpectrumType::from_code(self.sub_type),
+				values,
+				unit,
+				frequency: axes[0].clone(),
+				f_range: self.f_range,
+				bw: self.bw,
+			}),
+			(4, 2) => {
+				let mut time: Axis = axes[0].clone();
+				time.start += self.time_offset;
+				ProcSeries::TimeFrequency(TimeFrequency {
+					values,
+					unit,
+					time,
+					frequency: axes[1].clone(),
+					f_range: self.f_range,
+					bw: self.bw,
+				})
+			},
+			_ => ProcSeries::Other(values),
+		}
+	}
+
+	// bring the heterodyned data back to the original frequency band, the sampling step is
+	// given by the first axis of the data vector
+	pub fn restore_band(&self, vect: &FrVect) -> Vec<Complex<f64>> {
+		let dt: f64 = match vect.get_axes().first() {
+			Some(axis) => axis.dx,
+			None => panic!("The data vector has no dimension."),
+		};
+		restore_heterodyne(&vect.get_values().to_complex(), dt, self.f_shift, self.phase as f64)
+	}
+
+}
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrRawData {
+	length: u64,
+	instance: u32,
+	name: String,
+	first_ser: (u16, u32),
+	first_adc: (u16, u32),
+	first_table: (u16, u32),
+	log_msg: (u16, u32),
+	more: (u16, u32),
+}
+
+impl Reader for FrRawData {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		// structure pointers
+		let first_ser: (u16, u32) = read_ptr(iterator);
+		let first_adc: (u16, u32) = read_ptr(iterator);
+		let first_table: (u16, u32) = read_ptr(iterator);
+		let log_msg: (u16, u32) = read_ptr(iterator);
+		let more: (u16, u32) = read_ptr(iterator);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		FrRawData {
+			length,
+			instance,
+			name,
+			first_ser,
+			first_adc,
+			first_table,
+			log_msg,
+			more,
+		}
+	}
+	fn class(&self) -> u16 {
+		12
+	}
+}
+impl Writer for FrRawData {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrRawData", vec![("name", "STRING"), ("firstSer", "PTR_STRUCT(FrSerData *)"),
+			("firstAdc", "PTR_STRUCT(FrAdcData *)"), ("firstTable", "PTR_STRUCT(FrTable *)"),
+			("logMsg", "PTR_STRUCT(FrMsg *)"), ("more", "PTR_STRUCT(FrVect *)"),
+			("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		write_ptr(buffer, self.first_ser);
+		write_ptr(buffer, self.first_adc);
+		write_ptr(buffer, self.first_table);
+		write_ptr(buffer, self.log_msg);
+		write_ptr(buffer, self.more);
+	}
+}
+// getter functions
+impl FrRawData {
+	// raw data holding the ADC channels starting from first_adc
+	pub fn new(name: &str, first_adc: (u16, u32)) -> Self {
+		FrRawData {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			first_ser: (0, 0),
+			first_adc,
+			first_table: (0, 0),
+			log_msg: (0, 0),
+			more: (0, 0),
+		}
+	}
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_first_ser(&self) -> (u16, u32) {
+		self.first_ser
+	}
+	pub fn get_first_adc(&self) -> (u16, u32) {
+		self.first_adc
+	}
+	pub fn get_first_table(&self) -> (u16, u32) {
+		self.first_table
+	}
+	pub fn get_log_msg(&self) -> (u16, u32) {
+		self.log_msg
+	}
+	pub fn get_more(&self) -> (u16, u32) {
+		self.more
+	}
+}
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrSerData {
+	length: u64,
+	instance: u32,
+	name: String,
+	gps_sec: u32,
+	gps_nano: u32,
+	sample_rate: f64,
+	data: String,
+	serial: (u16, u32),
+	table: (u16, u32),
+	next: (u16, u32),
+}
+
+impl Reader for FrSerData {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		let gps_sec = read_u32(iterator);
+		//println!("time: {} s", gps_sec);
+		let gps_nano = read_u32(iterator);
+		//println!("residual: {} ns", gps_nano);
+		let sample_rate = read_f64(iterator);
+		//println!("sample rate: {}", sample_rate);
+		let data: String = read_one_string(iterator);
+		//println!("data: '{}'", data);
+		// structure pointers
+		let (class_serial, serial): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_serial, serial);
+		let (class_table, table): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_table, table);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		FrSerData {
+			length,
+			instance,
+			name,
+			gps_sec,
+			gps_nano,
+			sample_rate,
+			data,
+			serial: (class_serial, serial),
+			table: (class_table, table),
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		13
+	}
+}
+// getter functions
+impl FrSerData {
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_time(&self) -> f64 {
+		(self.gps_sec as f64) + (self.gps_nano as f64) * 1e-9
+	}
+	pub fn get_sample_rate(&self) -> f64 {
+		self.sample_rate
+	}
+	pub fn get_data(&self) -> String {
+		self.data.clone()
+	}
+	pub fn get_serial(&self) -> (u16, u32) {
+		self.serial
+	}
+	pub fn get_table(&self) -> (u16, u32) {
+		self.table
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+	// split the "name value name value ..." string into named channels, a name appearing several
+	// times gives successive samples; the values that are not numbers are ignored
+	pub fn get_channels(&self) -> Vec<(String, Vec<f64>)> {
+		let mut channels: Vec<(String, Vec<f64>)> = Vec::new();
+		let words: Vec<&str> = self.data.split_whitespace().collect();
+		for pair in words.chunks_exact(2) {
+			let value: f64 = match pair[1].parse() {
+				Ok(x) => x,
+				Err(_) => continue,
+			};
+			match channels.iter_mut().find(|(name, _)| name == pair[0]) {
+				Some((_, values)) => values.push(value),
+				None => channels.push((pair[0].to_string(), vec![value])),
+			}
+		}
+		channels
+	}
+	// samples of one channel of the string, as a vector sampled at the serial data rate and
+	// starting at the time of the structure relative to the frame start
+	pub fn get_channel(&self, name: &str, frame_start: f64) -> Option<FrVect> {
+		let (_, values) = self.get_channels().into_iter().find(|(x, _)| x == name)?;
+		let dx: f64 = if self.sample_rate > 0. { 1. / self.sample_rate } else { 0. };
+		Some(FrVect::new(name, VectData::F64(values), dx, self.get_time() - frame_start, "s", ""))
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrSimData {
+	length: u64,
+	instance: u32,
+	name: String,
+	comment: String,
+
+	sample_rate: f64,
+	time_offset: f64,
+	f_shift: f64,
+	phase: f32,
+
+	data: (u16, u32),
+	input: (u16, u32),
+	table: (u16, u32),
+	next: (u16, u32),
+}
+
+impl Reader for FrSimData {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		let comment: String = read_one_string(iterator);
+		//println!("comment: '{}'", comment);
+		let sample_rate = read_f64(iterator);
+		//println!("sample rate: {}", sample_rate);
+		let time_offset = read_f64(iterator);
+		//println!("time offset: {}", time_offset);
+		let f_shift = read_f64(iterator);
+		//println!("frequency shift: {}", f_shift);
+		let phase = read_f32(iterator);
+		//println!("phase: {}", phase);
+		// structure pointers
+		let (class_data, data): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_data, data);
+		let (class_input, input): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_input, input);
+		let (class_table, table): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_table, table);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		FrSimData {
+			length,
+			instance,
+			name,
+			comment,
+
+			sample_rate,
+			time_offset,
+			f_shift,
+			phase,
+
+			data: (class_data, data),
+			input: (class_input, input),
+			table: (class_table, table),
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		14
+	}
+}
+// getter functions
+impl FrSimData {
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_comment(&self) -> String {
+		self.comment.clone()
+	}
+	pub fn get_sample_rate(&self) -> f64 {
+		self.sample_rate
+	}
+	pub fn get_time_offset(&self) -> f64 {
+		self.time_offset
+	}
+	pub fn get_f_shift(&self) -> f64 {
+		self.f_shift
+	}
+	pub fn get_phase(&self) -> f32 {
+		self.phase
+	}
+	pub fn get_datavector_instance(&self) -> u32 {
+		self.data.1
+	}
+	pub fn get_datavector(&self) -> (u16, u32) {
+		self.data
+	}
+	pub fn get_input(&self) -> (u16, u32) {
+		self.input
+	}
+	pub fn get_table(&self) -> (u16, u32) {
+		self.table
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+	// bring the heterodyned data back to the original frequency band
+	pub fn restore_band(&self, vect: &FrVect) -> Vec<Complex<f64>> {
+		restore_heterodyne(&vect.get_values().to_complex(), 1. / self.sample_rate,
+			self.f_shift, self.phase as f64)
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrStatData {
+	length: u64,
+	instance: u32,
+	name: String,
+	comment: String,
+	representation: String,
+
+	time_start: u32,
+	time_end: u32,
+	version: u32,
+
+	detector: (u16, u32),
+	data: (u16, u32),
+	table: (u16, u32),
+}
+
+impl Reader for FrStatData {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		let comment: String = read_one_string(iterator);
+		//println!("comment: '{}'", comment);
+		let representation: String = read_one_string(iterator);
+		//println!("representation: '{}'", representation);
+		let time_start = read_u32(iterator);
+		//println!("start of validity: {} s", time_start);
+		let time_end = read_u32(iterator);
+		//println!("end of validity: {} s", time_end);
+		let version = read_u32(iterator);
+		//println!("version: {}", version);
+		// structure pointers
+		let (class_detector, detector): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_detector, detector);
+		let (class_data, data): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_data, data);
+		let (class_table, table): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_table, table);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		FrStatData {
+			length,
+			instance,
+			name,
+			comment,
+			representation,
 
+			time_start,
+			time_end,
+			version,
+
+			detector: (class_detector, detector),
 			data: (class_data, data),
-			next: (class_next, next)
+			table: (class_table, table),
 		}
 	}
 	fn class(&self) -> u16 {
-		11
+		16
 	}
 }
 // getter functions
-impl FrProcData {
-	
-	pub fn get_dump(&self) -> (u16, u16, f64, f64, f64, f32, f64, f64) {
-		(self.object_type, self.sub_type, self.time_offset, self.time_range, self.f_shift,
-		self.phase, self.f_range, self.bw)
+impl FrStatData {
+	pub fn get_instance(&self) -> u32 {
+		self.instance
 	}
-
-	pub fn get_datavector_instance(&self) -> u32 {
-		self.data.1
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_comment(&self) -> String {
+		self.comment.clone()
+	}
+	pub fn get_representation(&self) -> String {
+		self.representation.clone()
+	}
+	// validity range in gps seconds, an end equal to 0 means no end
+	pub fn get_validity(&self) -> (u32, u32) {
+		(self.time_start, self.time_end)
+	}
+	pub fn get_version(&self) -> u32 {
+		self.version
+	}
+	pub fn is_valid_at(&self, gps: f64) -> bool {
+		is_stat_valid(self.time_start, self.time_end, gps)
+	}
+	pub fn get_detector(&self) -> (u16, u32) {
+		self.detector
 	}
+	pub fn get_datavector(&self) -> (u16, u32) {
+		self.data
+	}
+	pub fn get_table(&self) -> (u16, u32) {
+		self.table
+	}
+}
+// a static data is valid from its start time, up to its end time if this one is not 0
+fn is_stat_valid(time_start: u32, time_end: u32, gps: f64) -> bool {
+	((time_start as f64) <= gps) & ((time_end == 0) | (gps < (time_end as f64)))
+}
+
+/* --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug)]
+pub struct FrSummary {
+	length: u64,
+	instance: u32,
+	name: String,
+	comment: String,
+	test: String,
+
+	moments: (u16, u32),
+	table: (u16, u32),
+	gps_sec: u32,
+	gps_nano: u32,
+	next: (u16, u32),
+}
+
+impl Reader for FrSummary {
+	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
+
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		let comment: String = read_one_string(iterator);
+		//println!("comment: '{}'", comment);
+		let test: String = read_one_string(iterator);
+		//println!("test: '{}'", test);
+		// structure pointers
+		let (class_moments, moments): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_moments, moments);
+		let (class_table, table): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_table, table);
+		let gps_sec = read_u32(iterator);
+		//println!("time: {} s", gps_sec);
+		let gps_nano = read_u32(iterator);
+		//println!("residual: {} ns", gps_nano);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
 
+		FrSummary {
+			length,
+			instance,
+			name,
+			comment,
+			test,
+
+			moments: (class_moments, moments),
+			table: (class_table, table),
+			gps_sec,
+			gps_nano,
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		17
+	}
+}
+// getter functions
+impl FrSummary {
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
-
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_comment(&self) -> String {
+		self.comment.clone()
+	}
+	// statistical test used to compute the moments
+	pub fn get_test(&self) -> String {
+		self.test.clone()
+	}
+	pub fn get_moments(&self) -> (u16, u32) {
+		self.moments
+	}
+	pub fn get_table(&self) -> (u16, u32) {
+		self.table
+	}
+	pub fn get_time(&self) -> f64 {
+		(self.gps_sec as f64) + (self.gps_nano as f64) * 1e-9
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
 }
+
 /* --------------------------------------------------------------------------------------------- */
-/*
+#[derive(PartialEq, Debug)]
 pub struct FrTable {
 	length: u64,
 	instance: u32,
+	name: String,
+	comment: String,
+
+	n_row: u32,
+	column_name: Vec<String>,
+
+	column: (u16, u32),
+	next: (u16, u32),
 }
 impl Reader for FrTable {
 	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
 
+		let name: String = read_one_string(iterator);
+		//println!("name: '{}'", name);
+		let comment: String = read_one_string(iterator);
+		//println!("comment: '{}'", comment);
+		let n_column: u16 = read_u16(iterator);
+		//println!("# columns: {}", n_column);
+		let n_row: u32 = read_u32(iterator);
+		//println!("# rows: {}", n_row);
+		let mut column_name: Vec<String> = Vec::new();
+		for _i in 0..n_column {
+			column_name.push(read_one_string(iterator));
+		}
+		//println!("columns: {:#?}", column_name);
+		// structure pointers
+		let (class_column, column): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_column, column);
+		let (class_next, next): (u16, u32) = read_ptr(iterator);
+		//println!("class: {}, instance: {}", class_next, next);
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		FrTable {
+			length,
+			instance,
+			name,
+			comment,
+
+			n_row,
+			column_name,
+
+			column: (class_column, column),
+			next: (class_next, next),
+		}
+	}
+	fn class(&self) -> u16 {
+		18
+	}
+}
+// getter functions
+impl FrTable {
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_comment(&self) -> String {
+		self.comment.clone()
+	}
+	pub fn get_n_row(&self) -> u32 {
+		self.n_row
+	}
+	pub fn get_column_names(&self) -> Vec<String> {
+		self.column_name.clone()
+	}
+	// first vector of the column chain, one vector per column
+	pub fn get_column(&self) -> (u16, u32) {
+		self.column
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
 	}
 }
-*/
 /* --------------------------------------------------------------------------------------------- */
-// TODO: add the structure types in the table of contents
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Default)]
 pub struct FrTOC {
 	length: u64,
 	instance: u32,
 	// frames
 	leap: u16,
+	data_quality: Vec<u32>,
 	gps_sec: Vec<u32>,
 	gps_nano: Vec<u32>,
 	dt: Vec<f64>,
+	run: Vec<u32>,
+	frame: Vec<u32>,
 	position: Vec<u64>,
+	position_first_adc: Vec<u64>,
+	position_first_ser: Vec<u64>,
+	position_first_table: Vec<u64>,
+	position_first_msg: Vec<u64>,
 	// FrSH
 	sh_id: Vec<u16>,
 	sh_name: Vec<String>,
+	// FrDetector
+	name_detector: Vec<String>,
+	position_detector: Vec<u64>,
+	// FrStatData
+	name_stat: Vec<String>,
+	detector_stat: Vec<String>,
+	n_stat: Vec<u32>,
+	time_start_stat: Vec<u32>,
+	time_end_stat: Vec<u32>,
+	version_stat: Vec<u32>,
+	position_stat: Vec<u64>,
 	// FrAdcData
 	name_adc: Vec<String>,
+	channel_id_adc: Vec<u32>,
+	group_id_adc: Vec<u32>,
 	position_adc: Vec<Vec<u64>>,
 	// FrProcData
 	name_proc: Vec<String>,
 	position_proc: Vec<Vec<u64>>,
+	// FrSimData
+	name_sim: Vec<String>,
+	position_sim: Vec<Vec<u64>>,
+	// FrSerData
+	name_ser: Vec<String>,
+	position_ser: Vec<Vec<u64>>,
+	// FrSummary
+	name_summary: Vec<String>,
+	position_summary: Vec<Vec<u64>>,
+	// FrEvent
+	name_event: Vec<String>,
+	n_event: Vec<u32>,
+	gps_sec_event: Vec<u32>,
+	gps_nano_event: Vec<u32>,
+	amplitude_event: Vec<f32>,
+	position_event: Vec<u64>,
+	// FrSimEvent
+	name_sim_event: Vec<String>,
+	n_sim_event: Vec<u32>,
+	gps_sec_sim_event: Vec<u32>,
+	gps_nano_sim_event: Vec<u32>,
+	amplitude_sim_event: Vec<f32>,
+	position_sim_event: Vec<u64>,
 }
 impl Reader for FrTOC {
 	fn read(iterator: &mut Bytes<BufReader<File>>,length: u64, instance: u32) -> Self {
@@ -705,58 +2783,77 @@ impl Reader for FrTOC {
 		// frames
 		let leap: u16 = read_u16(iterator);
 		//println!("time leap: {}", leap);
+		let mut data_quality: Vec<u32> = Vec::new();
 		let mut gps_sec: Vec<u32> = Vec::new();
 		let mut gps_nano: Vec<u32> = Vec::new();
 		let mut dt: Vec<f64> = Vec::new();
+		let mut run: Vec<u32> = Vec::new();
+		let mut frame: Vec<u32> = Vec::new();
 		let mut position: Vec<u64> = Vec::new();
 		// data parameters
 		let n_frames: u32 = read_u32(iterator);
 		//println!("# frames: {}", n_frames);
-		for _i in 0..n_frames { read_u32(iterator); } // data quality
+		for _i in 0..n_frames { data_quality.push(read_u32(iterator)); }
 		for _i in 0..n_frames { gps_sec.push(read_u32(iterator)); }
 		for _i in 0..n_frames { gps_nano.push(read_u32(iterator)); }
 		for _i in 0..n_frames { dt.push(read_f64(iterator)); }
-		for _i in 0..n_frames { read_u32(iterator); } // run
-		for _i in 0..n_frames { read_u32(iterator); } // frame
+		for _i in 0..n_frames { run.push(read_u32(iterator)); }
+		for _i in 0..n_frames { frame.push(read_u32(iterator)); }
 		for _i in 0..n_frames { position.push(read_u64(iterator)); }
 		// data position in bytes
-		for _i in 0..n_frames { read_u64(iterator); } // adc
-		for _i in 0..n_frames { read_u64(iterator); } // serial
-		for _i in 0..n_frames { read_u64(iterator); } // table
-		for _i in 0..n_frames { read_u64(iterator); } // message
+		let mut position_first_adc: Vec<u64> = Vec::new();
+		let mut position_first_ser: Vec<u64> = Vec::new();
+		let mut position_first_table: Vec<u64> = Vec::new();
+		let mut position_first_msg: Vec<u64> = Vec::new();
+		for _i in 0..n_frames { position_first_adc.push(read_u64(iterator)); }
+		for _i in 0..n_frames { position_first_ser.push(read_u64(iterator)); }
+		for _i in 0..n_frames { position_first_table.push(read_u64(iterator)); }
+		for _i in 0..n_frames { position_first_msg.push(read_u64(iterator)); }
 		// FrSH
 		let mut sh_id: Vec<u16> = Vec::new();
-		let sh_name: Vec<String> = Vec::new();
+		let mut sh_name: Vec<String> = Vec::new();
 		let n_sh: u16 = read_u16(iterator);
 		//println!("# headers: {}", n_sh);
 		for _i in 0..n_sh { sh_id.push(read_u16(iterator)); }
 		//println!("{:#?}", sh_id);
-		for _i in 0..n_sh { read_one_string(iterator); }
+		for _i in 0..n_sh { sh_name.push(read_one_string(iterator)); }
 		//println!("{:#?}", sh_name);
 		// FrDetector
+		let mut name_detector: Vec<String> = Vec::new();
+		let mut position_detector: Vec<u64> = Vec::new();
 		let n_detector: u32 = read_u32(iterator);
 		//println!("# detectors: {}", n_detector);
-		for _i in 0..n_detector { read_one_string(iterator); }
-		for _i in 0..n_detector { read_u64(iterator); }
+		for _i in 0..n_detector { name_detector.push(read_one_string(iterator)); }
+		for _i in 0..n_detector { position_detector.push(read_u64(iterator)); }
 		// FrStatData
-		let mut n_static: u32 = read_u32(iterator);
-		//println!("# static type: {}", n_static);
-		for _i in 0..n_static { read_one_string(iterator); }
-		for _i in 0..n_static { read_one_string(iterator); }
-		for _i in 0..n_static { read_u32(iterator); }
-		n_static = read_u32(iterator);
-		for _i in 0..n_static { read_u32(iterator); }
-		for _i in 0..n_static { read_u32(iterator); }
-		for _i in 0..n_static { read_u32(iterator); }
-		for _i in 0..n_static { read_u64(iterator); }
+		let mut name_stat: Vec<String> = Vec::new();
+		let mut detector_stat: Vec<String> = Vec::new();
+		let mut n_stat: Vec<u32> = Vec::new();
+		let mut time_start_stat: Vec<u32> = Vec::new();
+		let mut time_end_stat: Vec<u32> = Vec::new();
+		let mut version_stat: Vec<u32> = Vec::new();
+		let mut position_stat: Vec<u64> = Vec::new();
+		let n_stat_type: u32 = read_u32(iterator);
+		//println!("# static type: {}", n_stat_type);
+		for _i in 0..n_stat_type { name_stat.push(read_one_string(iterator)); }
+		for _i in 0..n_stat_type { detector_stat.push(read_one_string(iterator)); }
+		for _i in 0..n_stat_type { n_stat.push(read_u32(iterator)); }
+		let n_total_stat: u32 = read_u32(iterator);
+		//println!("# static: {}", n_total_stat);
+		for _i in 0..n_total_stat { time_start_stat.push(read_u32(iterator)); }
+		for _i in 0..n_total_stat { time_end_stat.push(read_u32(iterator)); }
+		for _i in 0..n_total_stat { version_stat.push(read_u32(iterator)); }
+		for _i in 0..n_total_stat { position_stat.push(read_u64(iterator)); }
 		// FrAdcData
 		let mut name_adc: Vec<String> = Vec::new();
+		let mut channel_id_adc: Vec<u32> = Vec::new();
+		let mut group_id_adc: Vec<u32> = Vec::new();
 		let mut position_adc: Vec<Vec<u64>> = Vec::new();
 		let n_adc: u32 = read_u32(iterator);
 		//println!("# adc: {}", n_adc);
 		for _i in 0..n_adc { name_adc.push(read_one_string(iterator)); }
-		for _i in 0..n_adc { read_u32(iterator); } // channel id
-		for _i in 0..n_adc { read_u32(iterator); } // group id
+		for _i in 0..n_adc { channel_id_adc.push(read_u32(iterator)); }
+		for _i in 0..n_adc { group_id_adc.push(read_u32(iterator)); }
 		for _i in 0..n_adc {
 			let mut one_vect: Vec<u64> = Vec::new();
 			for _j in 0..n_frames { one_vect.push(read_u64(iterator)); }
@@ -774,52 +2871,76 @@ impl Reader for FrTOC {
 			position_proc.push(one_vect);
 		}
 		// FrSimData
+		let mut name_sim: Vec<String> = Vec::new();
+		let mut position_sim: Vec<Vec<u64>> = Vec::new();
 		let n_sim: u32 = read_u32(iterator);
 		//println!("# simulations: {}", n_sim);
-		for _i in 0..n_sim { read_one_string(iterator); }
+		for _i in 0..n_sim { name_sim.push(read_one_string(iterator)); }
 		for _i in 0..n_sim {
-			for _j in 0..n_frames { read_u64(iterator); }
+			let mut one_vect: Vec<u64> = Vec::new();
+			for _j in 0..n_frames { one_vect.push(read_u64(iterator)); }
+			position_sim.push(one_vect);
 		}
 		// FrSerData
+		let mut name_ser: Vec<String> = Vec::new();
+		let mut position_ser: Vec<Vec<u64>> = Vec::new();
 		let n_ser: u32 = read_u32(iterator);
 		//println!("# serial: {}", n_ser);
-		for _i in 0..n_ser { read_one_string(iterator); }
+		for _i in 0..n_ser { name_ser.push(read_one_string(iterator)); }
 		for _i in 0..n_ser {
-			for _j in 0..n_frames { read_u64(iterator); }
+			let mut one_vect: Vec<u64> = Vec::new();
+			for _j in 0..n_frames { one_vect.push(read_u64(iterator)); }
+			position_ser.push(one_vect);
 		}
 		// FrSummary
+		let mut name_summary: Vec<String> = Vec::new();
+		let mut position_summary: Vec<Vec<u64>> = Vec::new();
 		let n_summary: u32 = read_u32(iterator);
 		//println!("# summary: {}", n_summary);
-		for _i in 0..n_summary { read_one_string(iterator); }
+		for _i in 0..n_summary { name_summary.push(read_one_string(iterator)); }
 		for _i in 0..n_summary {
-			for _j in 0..n_frames { read_u64(iterator); }
+			let mut one_vect: Vec<u64> = Vec::new();
+			for _j in 0..n_frames { one_vect.push(read_u64(iterator)); }
+			position_summary.push(one_vect);
 		}
 		// FrEvent
+		let mut name_event: Vec<String> = Vec::new();
+		let mut n_event: Vec<u32> = Vec::new();
+		let mut gps_sec_event: Vec<u32> = Vec::new();
+		let mut gps_nano_event: Vec<u32> = Vec::new();
+		let mut amplitude_event: Vec<f32> = Vec::new();
+		let mut position_event: Vec<u64> = Vec::new();
 		let n_event_type: u32 = read_u32(iterator);
 		//println!("# event type: {}", n_event_type);
-		for _i in 0..n_event_type { read_one_string(iterator); }
-		for _i in 0..n_event_type { read_u32(iterator); }
-
-		let n_event: u32 = read_u32(iterator);
-		//println!("# event: {}", n_event);
-		for _i in 0..n_event { read_u32(iterator); }
-		for _i in 0..n_event { read_u32(iterator); }
-		for _i in 0..n_event { read_f32(iterator); }
-		for _i in 0..n_event { read_u64(iterator); }
+		for _i in 0..n_event_type { name_event.push(read_one_string(iterator)); }
+		for _i in 0..n_event_type { n_event.push(read_u32(iterator)); }
+
+		let n_total_event: u32 = read_u32(iterator);
+		//println!("# event: {}", n_total_event);
+		for _i in 0..n_total_event { gps_sec_event.push(read_u32(iterator)); }
+		for _i in 0..n_total_event { gps_nano_event.push(read_u32(iterator)); }
+		for _i in 0..n_total_event { amplitude_event.push(read_f32(iterator)); }
+		for _i in 0..n_total_event { position_event.push(read_u64(iterator)); }
 		// FrSimEvent
-		let n_event_type: u32 = read_u32(iterator);
-		//println!("# simulated event type: {}", n_event);
-		for _i in 0..n_event_type { read_one_string(iterator); }
-		for _i in 0..n_event_type { read_u32(iterator); }
-
-		let n_event: u32 = read_u32(iterator);
-		//println!("# simulated event: {}", n_event);
-		for _i in 0..n_event { read_u32(iterator); }
-		for _i in 0..n_event { read_u32(iterator); }
-		for _i in 0..n_event { read_f32(iterator); }
-		for _i in 0..n_event { read_u64(iterator); }
+		let mut name_sim_event: Vec<String> = Vec::new();
+		let mut n_sim_event: Vec<u32> = Vec::new();
+		let mut gps_sec_sim_event: Vec<u32> = Vec::new();
+		let mut gps_nano_sim_event: Vec<u32> = Vec::new();
+		let mut amplitude_sim_event: Vec<f32> = Vec::new();
+		let mut position_sim_event: Vec<u64> = Vec::new();
+		let n_sim_event_type: u32 = read_u32(iterator);
+		//println!("# simulated event type: {}", n_sim_event_type);
+		for _i in 0..n_sim_event_type { name_sim_event.push(read_one_string(iterator)); }
+		for _i in 0..n_sim_event_type { n_sim_event.push(read_u32(iterator)); }
+
+		let n_total_sim_event: u32 = read_u32(iterator);
+		//println!("# simulated event: {}", n_total_sim_event);
+		for _i in 0..n_total_sim_event { gps_sec_sim_event.push(read_u32(iterator)); }
+		for _i in 0..n_total_sim_event { gps_nano_sim_event.push(read_u32(iterator)); }
+		for _i in 0..n_total_sim_event { amplitude_sim_event.push(read_f32(iterator)); }
+		for _i in 0..n_total_sim_event { position_sim_event.push(read_u64(iterator)); }
 		
-		let check_sum: u32 = read_u32(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
 
 		// create struct
@@ -828,31 +2949,385 @@ impl Reader for FrTOC {
 			instance,
 			// frames
 			leap,
+			data_quality,
 			gps_sec,
 			gps_nano,
 			dt,
+			run,
+			frame,
 			position,
+			position_first_adc,
+			position_first_ser,
+			position_first_table,
+			position_first_msg,
 			// FrSH
 			sh_id,
 			sh_name,
+			// FrDetector
+			name_detector,
+			position_detector,
+			// FrStatData
+			name_stat,
+			detector_stat,
+			n_stat,
+			time_start_stat,
+			time_end_stat,
+			version_stat,
+			position_stat,
 			// FrAdcData
 			name_adc,
+			channel_id_adc,
+			group_id_adc,
 			position_adc,
 			// FrProcData
 			name_proc,
 			position_proc,
-
+			// FrSimData
+			name_sim,
+			position_sim,
+			// FrSerData
+			name_ser,
+			position_ser,
+			// FrSummary
+			name_summary,
+			position_summary,
+			// FrEvent
+			name_event,
+			n_event,
+			gps_sec_event,
+			gps_nano_event,
+			amplitude_event,
+			position_event,
+			// FrSimEvent
+			name_sim_event,
+			n_sim_event,
+			gps_sec_sim_event,
+			gps_nano_sim_event,
+			amplitude_sim_event,
+			position_sim_event,
 		}
 	}
 	fn class(&self) -> u16 {
 		19
 	}
 }
+impl Writer for FrTOC {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrTOC", vec![("ULeapS", "INT_2S"), ("nFrame", "INT_4U"),
+			("dataQuality", "INT_4U[nFrame]"), ("GTimeS", "INT_4U[nFrame]"),
+			("GTimeN", "INT_4U[nFrame]"), ("dt", "REAL_8[nFrame]"), ("runs", "INT_4S[nFrame]"),
+			("frame", "INT_4U[nFrame]"), ("positionH", "INT_8U[nFrame]"),
+			("nFirstADC", "INT_8U[nFrame]"), ("nFirstSer", "INT_8U[nFrame]"),
+			("nFirstTable", "INT_8U[nFrame]"), ("nFirstMsg", "INT_8U[nFrame]"),
+			("nSH", "INT_2U"), ("SHid", "INT_2U[nSH]"), ("SHname", "STRING[nSH]"),
+			("nDetector", "INT_4U"), ("nameDetector", "STRING[nDetector]"),
+			("positionDetector", "INT_8U[nDetector]"),
+			("nStatType", "INT_4U"), ("nameStat", "STRING[nStatType]"),
+			("detector", "STRING[nStatType]"), ("nStatInstance", "INT_4U[nStatType]"),
+			("nTotalStat", "INT_4U"), ("tStart", "INT_4U[nTotalStat]"),
+			("tEnd", "INT_4U[nTotalStat]"), ("version", "INT_4U[nTotalStat]"),
+			("positionStat", "INT_8U[nTotalStat]"),
+			("nADC", "INT_4U"), ("name", "STRING[nADC]"), ("channelID", "INT_4U[nADC]"),
+			("groupID", "INT_4U[nADC]"), ("positionADC", "INT_8U[nADC][nFrame]"),
+			("nProc", "INT_4U"), ("nameProc", "STRING[nProc]"),
+			("positionProc", "INT_8U[nProc][nFrame]"),
+			("nSim", "INT_4U"), ("nameSim", "STRING[nSim]"),
+			("positionSim", "INT_8U[nSim][nFrame]"),
+			("nSer", "INT_4U"), ("nameSer", "STRING[nSer]"),
+			("positionSer", "INT_8U[nSer][nFrame]"),
+			("nSummary", "INT_4U"), ("nameSum", "STRING[nSummary]"),
+			("positionSum", "INT_8U[nSummary][nFrame]"),
+			("nEventType", "INT_4U"), ("nameEvent", "STRING[nEventType]"),
+			("nEvent", "INT_4U[nEventType]"), ("nTotalEvent", "INT_4U"),
+			("GTimeSEvent", "INT_4U[nTotalEvent]"), ("GTimeNEvent", "INT_4U[nTotalEvent]"),
+			("amplitudeEvent", "REAL_4[nTotalEvent]"), ("positionEvent", "INT_8U[nTotalEvent]"),
+			("nSimEventType", "INT_4U"), ("nameSimEvent", "STRING[nSimEventType]"),
+			("nSimEvent", "INT_4U[nSimEventType]"), ("nTotalSEvent", "INT_4U"),
+			("GTimeSSim", "INT_4U[nTotalSEvent]"), ("GTimeNSim", "INT_4U[nTotalSEvent]"),
+			("amplitudeSimEvent", "REAL_4[nTotalSEvent]"),
+			("positionSimEvent", "INT_8U[nTotalSEvent]"), ("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+
+		// frames
+		write_u16(buffer, self.leap);
+		let n_frames: usize = self.get_n_frames();
+		write_u32(buffer, n_frames as u32);
+		for x in self.data_quality.iter() { write_u32(buffer, *x); }
+		for x in self.gps_sec.iter() { write_u32(buffer, *x); }
+		for x in self.gps_nano.iter() { write_u32(buffer, *x); }
+		for x in self.dt.iter() { write_f64(buffer, *x); }
+		for x in self.run.iter() { write_u32(buffer, *x); }
+		for x in self.frame.iter() { write_u32(buffer, *x); }
+		for x in self.position.iter() { write_u64(buffer, *x); }
+		for x in self.position_first_adc.iter() { write_u64(buffer, *x); }
+		for x in self.position_first_ser.iter() { write_u64(buffer, *x); }
+		for x in self.position_first_table.iter() { write_u64(buffer, *x); }
+		for x in self.position_first_msg.iter() { write_u64(buffer, *x); }
+		// FrSH
+		write_u16(buffer, self.sh_id.len() as u16);
+		for x in self.sh_id.iter() { write_u16(buffer, *x); }
+		for x in self.sh_name.iter() { write_one_string(buffer, x); }
+		// FrDetector
+		write_u32(buffer, self.name_detector.len() as u32);
+		for x in self.name_detector.iter() { write_one_string(buffer, x); }
+		for x in self.position_detector.iter() { write_u64(buffer, *x); }
+		// FrStatData
+		write_u32(buffer, self.name_stat.len() as u32);
+		for x in self.name_stat.iter() { write_one_string(buffer, x); }
+		for x in self.detector_stat.iter() { write_one_string(buffer, x); }
+		for x in self.n_stat.iter() { write_u32(buffer, *x); }
+		write_u32(buffer, self.position_stat.len() as u32);
+		for x in self.time_start_stat.iter() { write_u32(buffer, *x); }
+		for x in self.time_end_stat.iter() { write_u32(buffer, *x); }
+		for x in self.version_stat.iter() { write_u32(buffer, *x); }
+		for x in self.position_stat.iter() { write_u64(buffer, *x); }
+		// FrAdcData
+		write_u32(buffer, self.name_adc.len() as u32);
+		for x in self.name_adc.iter() { write_one_string(buffer, x); }
+		for x in self.channel_id_adc.iter() { write_u32(buffer, *x); }
+		for x in self.group_id_adc.iter() { write_u32(buffer, *x); }
+		write_frame_positions(buffer, &self.position_adc, n_frames);
+		// FrProcData, FrSimData, FrSerData and FrSummary
+		for (names, positions) in [(&self.name_proc, &self.position_proc),
+			(&self.name_sim, &self.position_sim), (&self.name_ser, &self.position_ser),
+			(&self.name_summary, &self.position_summary)] {
+			write_u32(buffer, names.len() as u32);
+			for x in names.iter() { write_one_string(buffer, x); }
+			write_frame_positions(buffer, positions, n_frames);
+		}
+		// FrEvent
+		write_u32(buffer, self.name_event.len() as u32);
+		for x in self.name_event.iter() { write_one_string(buffer, x); }
+		for x in self.n_event.iter() { write_u32(buffer, *x); }
+		write_u32(buffer, self.position_event.len() as u32);
+		for x in self.gps_sec_event.iter() { write_u32(buffer, *x); }
+		for x in self.gps_nano_event.iter() { write_u32(buffer, *x); }
+		for x in self.amplitude_event.iter() { write_f32(buffer, *x); }
+		for x in self.position_event.iter() { write_u64(buffer, *x); }
+		// FrSimEvent
+		write_u32(buffer, self.name_sim_event.len() as u32);
+		for x in self.name_sim_event.iter() { write_one_string(buffer, x); }
+		for x in self.n_sim_event.iter() { write_u32(buffer, *x); }
+		write_u32(buffer, self.position_sim_event.len() as u32);
+		for x in self.gps_sec_sim_event.iter() { write_u32(buffer, *x); }
+		for x in self.gps_nano_sim_event.iter() { write_u32(buffer, *x); }
+		for x in self.amplitude_sim_event.iter() { write_f32(buffer, *x); }
+		for x in self.position_sim_event.iter() { write_u64(buffer, *x); }
+	}
+}
+// write the position of each channel in each frame, 0 when a channel is missing from a frame
+fn write_frame_positions(buffer: &mut Vec<u8>, positions: &[Vec<u64>], n_frames: usize) {
+	for channel in positions.iter() {
+		for j in 0..n_frames {
+			write_u64(buffer, channel.get(j).copied().unwrap_or(0));
+		}
+	}
+}
+// position of a channel in a frame, the channel being added to the index if needed
+fn set_frame_position(names: &mut Vec<String>, positions: &mut Vec<Vec<u64>>, name: String,
+	frame: usize, position: u64) -> usize {
+
+	let index: usize = match names.iter().position(|x| *x == name) {
+		Some(i) => i,
+		None => {
+			names.push(name);
+			positions.push(Vec::new());
+			names.len() - 1
+		},
+	};
+	if positions[index].len() <= frame {
+		positions[index].resize(frame + 1, 0);
+	}
+	positions[index][frame] = position;
+	index
+}
 impl FrTOC {
+	// empty table of contents, filled while the file is written
+	pub fn new() -> Self {
+		FrTOC::default()
+	}
+	// add a frame written at position, with its first FrAdcData at first_adc (0 if none)
+	pub(crate) fn add_frame(&mut self, header: &FrameH, position: u64, first_adc: u64) {
+		self.leap = header.u_leap;
+		self.data_quality.push(header.data_quality);
+		self.gps_sec.push(header.gps_sec);
+		self.gps_nano.push(header.gps_nano);
+		self.dt.push(header.dt);
+		self.run.push(header.run);
+		self.frame.push(header.frame);
+		self.position.push(position);
+		self.position_first_adc.push(first_adc);
+		self.position_first_ser.push(0);
+		self.position_first_table.push(0);
+		self.position_first_msg.push(0);
+	}
+	// add an ADC channel written at position in the last frame
+	pub(crate) fn add_adc(&mut self, adc: &FrAdcData, position: u64) {
+		let frame: usize = self.get_n_frames() - 1;
+		let index: usize = set_frame_position(&mut self.name_adc, &mut self.position_adc,
+			adc.name.clone(), frame, position);
+		if index == self.channel_id_adc.len() {
+			self.channel_id_adc.push(adc.ch_num);
+			self.group_id_adc.push(adc.ch_group);
+		}
+	}
+	// add a processed channel written at position in the last frame
+	pub(crate) fn add_proc(&mut self, proc: &FrProcData, position: u64) {
+		let frame: usize = self.get_n_frames() - 1;
+		set_frame_position(&mut self.name_proc, &mut self.position_proc, proc.name.clone(),
+			frame, position);
+	}
+	// add a detector written at position, a detector repeated in each frame is indexed once
+	pub(crate) fn add_detector(&mut self, detector: &FrDetector, position: u64) {
+		if self.name_detector.contains(&detector.name) {
+			return;
+		}
+		self.name_detector.push(detector.name.clone());
+		self.position_detector.push(position);
+	}
+	// add an event written at position, the events being kept sorted by type
+	pub(crate) fn add_event(&mut self, event: &FrEvent, position: u64) {
+		let index: usize = match self.name_event.iter().position(|x| *x == event.name) {
+			Some(i) => i,
+			None => {
+				self.name_event.push(event.name.clone());
+				self.n_event.push(0);
+				self.name_event.len() - 1
+			},
+		};
+		let i: usize = self.n_event[0..=index].iter().sum::<u32>() as usize;
+		self.n_event[index] += 1;
+		self.gps_sec_event.insert(i, event.gps_sec);
+		self.gps_nano_event.insert(i, event.gps_nano);
+		self.amplitude_event.insert(i, event.amplitude);
+		self.position_event.insert(i, position);
+	}
+	// structure types described in the file
+	pub(crate) fn set_dictionary(&mut self, dictionary: Vec<(u16, String)>) {
+		(self.sh_id, self.sh_name) = dictionary.into_iter().unzip();
+	}
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
+	// number of leap seconds between GPS and TAI
+	pub fn get_leap(&self) -> u16 {
+		self.leap
+	}
+	pub fn get_n_frames(&self) -> usize {
+		self.position.len()
+	}
+	// frames of the file: run number, frame number, data quality, gps start, duration and position
+	pub fn get_frames(&self) -> Vec<(u32, u32, u32, f64, f64, u64)> {
+		(0..self.get_n_frames()).map(|i| {
+			let start: f64 = (self.gps_sec[i] as f64) + (self.gps_nano[i] as f64) * 1e-9;
+			(self.run[i], self.frame[i], self.data_quality[i], start, self.dt[i], self.position[i])
+		}).collect()
+	}
+	// index of the frame containing the gps time
+	pub fn find_frame(&self, gps: f64) -> Option<usize> {
+		self.get_frames().iter().position(|(_, _, _, start, dt, _)|
+			(*start <= gps) & (gps < start + dt))
+	}
+	// positions of the first FrAdcData, FrSerData, FrTable and FrMsg of each frame
+	pub fn get_first_positions(&self) -> Vec<(u64, u64, u64, u64)> {
+		(0..self.get_n_frames()).map(|i| (self.position_first_adc[i], self.position_first_ser[i],
+			self.position_first_table[i], self.position_first_msg[i])).collect()
+	}
+	// dictionary of the file: class number and name of each structure type
+	pub fn get_dictionary(&self) -> Vec<(u16, String)> {
+		self.sh_id.iter().cloned().zip(self.sh_name.iter().cloned()).collect()
+	}
+	// names of the detectors, with their position in the file
+	pub fn get_detector_index(&self) -> Vec<(String, u64)> {
+		self.name_detector.iter().cloned().zip(self.position_detector.iter().cloned()).collect()
+	}
+	// names of the ADC channels, with their channel id, group id and position in each frame
+	pub fn get_adc_index(&self) -> Vec<(String, u32, u32, Vec<u64>)> {
+		(0..self.name_adc.len()).map(|i| (self.name_adc[i].clone(), self.channel_id_adc[i],
+			self.group_id_adc[i], self.position_adc[i].clone())).collect()
+	}
+	// names of the processed channels, with their position in each frame
+	pub fn get_proc_index(&self) -> Vec<(String, Vec<u64>)> {
+		self.name_proc.iter().cloned().zip(self.position_proc.iter().cloned()).collect()
+	}
+	// names of the simulated channels, with their position in each frame
+	pub fn get_sim_index(&self) -> Vec<(String, Vec<u64>)> {
+		self.name_sim.iter().cloned().zip(self.position_sim.iter().cloned()).collect()
+	}
+	// inventory of the channels of the file: name and class of the structure holding the channel
+	// (4 for FrAdcData, 11 for FrProcData, 14 for FrSimData)
+	pub fn get_channels(&self) -> Vec<(String, u16)> {
+		let adc = self.name_adc.iter().map(|x| (x.clone(), 4));
+		let proc = self.name_proc.iter().map(|x| (x.clone(), 11));
+		let sim = self.name_sim.iter().map(|x| (x.clone(), 14));
+		adc.chain(proc).chain(sim).collect()
+	}
+	// index of the static data: name, detector, start and end of validity, version and position
+	// in the file
+	pub fn get_stat_index(&self) -> Vec<(String, String, u32, u32, u32, u64)> {
+		check_index_size("static data", &self.n_stat, &[self.time_start_stat.len(),
+			self.time_end_stat.len(), self.version_stat.len(), self.position_stat.len()]);
+		// the instances are sorted by type
+		let types = self.name_stat.iter().zip(self.detector_stat.iter()).zip(self.n_stat.iter())
+			.flat_map(|(x, n)| std::iter::repeat_n(x, *n as usize));
+		types.enumerate().map(|(i, (name, detector))| {
+			(name.clone(), detector.clone(), self.time_start_stat[i], self.time_end_stat[i],
+				self.version_stat[i], self.position_stat[i])
+		}).collect()
+	}
+	// position of the static data called name of a detector, with the highest version valid
+	// at the gps time
+	pub fn find_stat(&self, name: &str, detector: &str, gps: f64) -> Option<u64> {
+		self.get_stat_index().into_iter()
+			.filter(|(x, y, start, end, _, _)| (x == name) & (y == detector)
+				& is_stat_valid(*start, *end, gps))
+			.max_by_key(|(_, _, _, _, version, _)| *version)
+			.map(|(_, _, _, _, _, position)| position)
+	}
+	// names of the serial data, with their position in each frame
+	pub fn get_serial_index(&self) -> Vec<(String, Vec<u64>)> {
+		self.name_ser.iter().cloned().zip(self.position_ser.iter().cloned()).collect()
+	}
+	// names of the summaries, with their position in each frame
+	pub fn get_summary_index(&self) -> Vec<(String, Vec<u64>)> {
+		self.name_summary.iter().cloned().zip(self.position_summary.iter().cloned()).collect()
+	}
+	// index of the events: name, gps time, amplitude and position in the file of each event
+	pub fn get_event_index(&self) -> Vec<(String, f64, f32, u64)> {
+		build_event_index("event", &self.name_event, &self.n_event, &self.gps_sec_event,
+			&self.gps_nano_event, &self.amplitude_event, &self.position_event)
+	}
+	// index of the simulated events, with the same layout as the index of the events
+	pub fn get_sim_event_index(&self) -> Vec<(String, f64, f32, u64)> {
+		build_event_index("simulated event", &self.name_sim_event, &self.n_sim_event,
+			&self.gps_sec_sim_event, &self.gps_nano_sim_event, &self.amplitude_sim_event,
+			&self.position_sim_event)
+	}
+
+}
+// expand the event index of the table of contents, where the events are sorted by type
+fn build_event_index(index: &str, names: &[String], counts: &[u32], gps_sec: &[u32],
+	gps_nano: &[u32], amplitude: &[f32], position: &[u64]) -> Vec<(String, f64, f32, u64)> {
+
+	check_index_size(index, counts, &[gps_sec.len(), gps_nano.len(), amplitude.len(),
+		position.len()]);
+	let names = names.iter().zip(counts.iter())
+		.flat_map(|(name, n)| std::iter::repeat_n(name.clone(), *n as usize));
+	names.enumerate().map(|(i, name)| {
+		let time: f64 = (gps_sec[i] as f64) + (gps_nano[i] as f64) * 1e-9;
+		(name, time, amplitude[i], position[i])
+	}).collect()
+}
 
+// check that the arrays of an index of the table of contents, sorted by type, hold an entry for
+// each instance counted by type
+fn check_index_size(index: &str, counts: &[u32], sizes: &[usize]) {
+	let n: u64 = counts.iter().map(|x| *x as u64).sum();
+	if let Some(size) = sizes.iter().find(|x| (**x as u64) < n) {
+		panic!("The {} index of the table of contents counts {} instances but holds {}.", index,
+			n, size);
+	}
 }
 /* --------------------------------------------------------------------------------------------- */
 #[derive(PartialEq, Debug)]
@@ -908,7 +3383,7 @@ impl Reader for FrVect {
 		//println!("unit: '{}'", unit_y);
 		let (class_next, next): (u16, u32) = read_ptr(iterator);
 		//println!("class: {}, instance: {}", class_next, next);
-		let check_sum: u32 = read_u32(iterator);
+		let _check_sum: u32 = read_u32(iterator);
 		//println!("check sum: {}", check_sum);
 		
 		// create structure
@@ -937,15 +3412,251 @@ impl Reader for FrVect {
 		20
 	}
 }
+impl Writer for FrVect {
+	fn dictionary() -> (&'static str, Vec<(&'static str, &'static str)>) {
+		("FrVect", vec![("name", "STRING"), ("compress", "INT_2U"), ("type", "INT_2U"),
+			("nData", "INT_8U"), ("nBytes", "INT_8U"), ("data", "CHAR[nBytes]"),
+			("nDim", "INT_4U"), ("nx", "INT_8U[nDim]"), ("dx", "REAL_8[nDim]"),
+			("startX", "REAL_8[nDim]"), ("unitX", "STRING[nDim]"), ("unitY", "STRING"),
+			("next", "PTR_STRUCT(FrVect *)"), ("chkSum", "INT_4U")])
+	}
+	fn write(&self, buffer: &mut Vec<u8>) {
+		write_one_string(buffer, &self.name);
+		write_u16(buffer, self.compress);
+		write_u16(buffer, self.data_type);
+		write_u64(buffer, self.n_data);
+		write_u64(buffer, self.data.len() as u64);
+		buffer.extend_from_slice(&self.data);
+		write_u32(buffer, self.n_dim);
+		for x in self.dim.iter() { write_u64(buffer, *x); }
+		for x in self.dx.iter() { write_f64(buffer, *x); }
+		for x in self.start_x.iter() { write_f64(buffer, *x); }
+		for x in self.unit_x.iter() { write_one_string(buffer, x); }
+		write_one_string(buffer, &self.unit_y);
+		write_ptr(buffer, self.next);
+	}
+}
 impl FrVect {
+	// create a one dimensional uncompressed vector, the data being little endian
+	pub fn new(name: &str, values: VectData, dx: f64, start_x: f64, unit_x: &str, unit_y: &str)
+		-> Self {
+
+		let n_data: u64 = values.len() as u64;
+		let (data_type, data): (u16, Vec<u8>) = values.encode();
+		FrVect {
+			length: 0,
+			instance: 0,
+			name: name.to_string(),
+			compress: 0x100,
+
+			data_type,
+			n_data,
+			n_bytes: data.len() as u64,
+			data,
+
+			n_dim: 1,
+			dim: vec![n_data],
+			dx: vec![dx],
+			start_x: vec![start_x],
+			unit_x: vec![unit_x.to_string()],
+			unit_y: unit_y.to_string(),
+
+			next: (0, 0),
+		}
+	}
+	pub(crate) fn set_next(&mut self, next: (u16, u32)) {
+		self.next = next;
+	}
+	// compress the data, the vector being decoded first if it is already compressed or big endian
+	pub fn compress(&mut self, compression: &compression::Compression) {
+		let data: Vec<u8> = match self.compress {
+			0x100 => std::mem::take(&mut self.data),
+			_ => self.get_values().encode().1,
+		};
+		let (compress, data): (u16, Vec<u8>) = compression.compress(self.data_type, &data);
+		self.compress = compress;
+		self.n_bytes = data.len() as u64;
+		self.data = data;
+	}
 	pub fn get_instance(&self) -> u32 {
 		self.instance
 	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
 	pub fn get_compress(&self) -> u16 {
 		self.compress
 	}
 	pub fn get_data(&self) -> Vec<u8> {
 		self.data.clone()
 	}
+	pub fn get_data_type(&self) -> u16 {
+		self.data_type
+	}
+	pub fn get_next(&self) -> (u16, u32) {
+		self.next
+	}
+	// decode the data bytes according to the data type
+	pub fn get_values(&self) -> VectData {
+		VectData::decode(self.data_type, self.compress, &self.data, self.n_data)
+	}
+	pub fn get_shape(&self) -> Vec<usize> {
+		self.dim.iter().map(|x| *x as usize).collect()
+	}
+	pub fn get_axes(&self) -> Vec<Axis> {
+		(0..self.n_dim as usize).map(|i| Axis {
+			n: self.dim[i],
+			dx: self.dx[i],
+			start: self.start_x[i],
+			unit: Unit::parse(&self.unit_x[i]),
+		}).collect()
+	}
+	pub fn get_unit_y(&self) -> Unit {
+		Unit::parse(&self.unit_y)
+	}
+	// decoded data with their unit
+	pub fn get_series(&self) -> Series {
+		Series::new(self.get_values().to_f64(), self.get_unit_y())
+	}
+	// n-dimensional view of the data, the last axis is the one varying the fastest
+	#[cfg(feature = "ndarray")]
+	pub fn get_array(&self) -> ndarray::ArrayD<f64> {
+		let shape = ndarray::IxDyn(&self.get_shape());
+		match ndarray::ArrayD::from_shape_vec(shape, self.get_values().to_f64()) {
+			Ok(x) => x,
+			Err(e) => panic!("The data does not match the vector dimensions: {}", e),
+		}
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- */
+// structure of a class not modeled by the crate: the bytes between its common part and its check
+// sum, with the name and the elements (name and type) given by its dictionary entries
+#[derive(PartialEq, Debug, Clone)]
+pub struct FrUnknown {
+	length: u64,
+	instance: u32,
+	class: u16,
+	name: String,
+	elements: Vec<(String, String)>,
+	data: Vec<u8>,
 }
 
+impl FrUnknown {
+	pub fn read(iterator: &mut Bytes<BufReader<File>>, length: u64, instance: u32, class: u16)
+		-> Self {
+
+		// the common part is 14 bytes long and the check sum 4 bytes long
+		let mut data: Vec<u8> = Vec::new();
+		for _i in 0..length.saturating_sub(18) {
+			data.push(read_one_byte(iterator));
+		}
+		let _check_sum: u32 = read_u32(iterator);
+		//println!("check sum: {}", check_sum);
+
+		FrUnknown {
+			length,
+			instance,
+			class,
+			name: String::new(),
+			elements: Vec::new(),
+			data,
+		}
+	}
+	pub(crate) fn set_dictionary(&mut self, name: &str, elements: &[(String, String)]) {
+		self.name = name.to_string();
+		self.elements = elements.to_vec();
+	}
+	pub fn get_instance(&self) -> u32 {
+		self.instance
+	}
+	pub fn get_class(&self) -> u16 {
+		self.class
+	}
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+	pub fn get_elements(&self) -> Vec<(String, String)> {
+		self.elements.clone()
+	}
+	pub fn get_data(&self) -> Vec<u8> {
+		self.data.clone()
+	}
+	// pointers held by the structure, None if its elements do not describe its bytes
+	pub fn get_pointers(&self) -> Option<Vec<(u16, u32)>> {
+		Some(self.find_pointers()?.into_iter()
+			.map(|i| (u16::from_le_bytes([self.data[i], self.data[i + 1]]),
+				u32::from_le_bytes(self.data[i + 2..i + 6].try_into().unwrap())))
+			.collect())
+	}
+	// replace the pointers held by the structure, in the order given by get_pointers
+	pub(crate) fn set_pointers(&mut self, pointers: &[(u16, u32)]) {
+		for (i, pointer) in self.find_pointers().unwrap_or_default().into_iter().zip(pointers) {
+			let mut bytes: Vec<u8> = Vec::new();
+			write_ptr(&mut bytes, *pointer);
+			self.data[i..i + 6].copy_from_slice(&bytes);
+		}
+	}
+
+	// offsets of the pointers in the bytes, found by walking through the elements; the size of
+	// the arrays are given by numbers or by the value of previous elements
+	fn find_pointers(&self) -> Option<Vec<usize>> {
+
+		let mut values: BTreeMap<&str, u64> = BTreeMap::new();
+		let mut pointers: Vec<usize> = Vec::new();
+		let mut offset: usize = 0;
+		for (name, element_type) in self.elements.iter() {
+			// the check sum is not part of the bytes
+			if (name == "chkSum") & (offset == self.data.len()) {
+				break;
+			}
+			let (base, dims): (&str, &str) = match element_type.find('[') {
+				Some(i) => element_type.split_at(i),
+				None => (element_type.as_str(), ""),
+			};
+			let mut n: u64 = 1;
+			for dim in dims.split(['[', ']']).filter(|x| !x.trim().is_empty()) {
+				n *= match dim.trim().parse::<u64>() {
+					Ok(x) => x,
+					Err(_) => *values.get(dim.trim())?,
+				};
+			}
+			let base: &str = base.trim();
+			let size: usize = match base {
+				"CHAR" | "CHAR_U" => 1,
+				"INT_2S" | "INT_2U" => 2,
+				"INT_4S" | "INT_4U" | "REAL_4" => 4,
+				"INT_8S" | "INT_8U" | "REAL_8" | "COMPLEX_8" => 8,
+				"COMPLEX_16" => 16,
+				"STRING" => {
+					for _i in 0..n {
+						let length: u16 = u16::from_le_bytes(
+							self.data.get(offset..offset + 2)?.try_into().unwrap());
+						offset += 2 + length as usize;
+					}
+					continue;
+				},
+				x if x.contains("PTR_STRUCT") => {
+					for _i in 0..n {
+						pointers.push(offset);
+						offset += 6;
+					}
+					continue;
+				},
+				_ => return None,
+			};
+			// keep the integers which may give the size of the next arrays
+			if (dims.is_empty()) & base.starts_with("INT") {
+				let bytes: &[u8] = self.data.get(offset..offset + size)?;
+				let mut word: [u8; 8] = [0; 8];
+				word[..size].copy_from_slice(bytes);
+				values.insert(name, u64::from_le_bytes(word));
+			}
+			offset += size * n as usize;
+		}
+		if offset != self.data.len() {
+			return None;
+		}
+		Some(pointers)
+	}
+}
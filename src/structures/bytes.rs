@@ -27,12 +27,12 @@ pub fn read_one_string(iterator: &mut Bytes<BufReader<File>>) -> String {
 		};
 		binary.push(value);
 	}
-	// cast it into a string
+	// cast it into a string, without the null terminating character
 	let s = match str::from_utf8(&binary) {
 		Ok(v) => v,
 		Err(e) => panic!("Invalid UTF-8 sequence: {}", e),
 	};
-	s.to_string()
+	s.trim_end_matches('\0').to_string()
 }
 
 pub fn read_one_byte(iterator: &mut Bytes<BufReader<File>>) -> u8 {
@@ -61,6 +61,15 @@ pub fn read_u32(iterator: &mut Bytes<BufReader<File>>) -> u32 {
 
 	u32::from_le_bytes(binary.try_into().unwrap())
 }
+pub fn read_i32(iterator: &mut Bytes<BufReader<File>>) -> i32 {
+	
+	let mut binary: Vec<u8> = Vec::new();
+	for _i in 0..4 {
+		binary.push(iterator.next().unwrap().unwrap());
+	}
+
+	i32::from_le_bytes(binary.try_into().unwrap())
+}
 pub fn read_u64(iterator: &mut Bytes<BufReader<File>>) -> u64 {
 	
 	let mut binary: Vec<u8> = Vec::new();
@@ -95,7 +104,63 @@ pub fn read_ptr(iterator: &mut Bytes<BufReader<File>>) -> (u16, u32) {
 	(class, instance)
 }
 
+/* --------------------------------------------------------------------------------------------- *
+ * write types
+ * --------------------------------------------------------------------------------------------- */
+pub fn write_one_string(buffer: &mut Vec<u8>, value: &str) {
 
+	// the length includes the null terminating character
+	write_u16(buffer, (value.len() + 1) as u16);
+	buffer.extend_from_slice(value.as_bytes());
+	buffer.push(0);
+}
 
+pub fn write_one_byte(buffer: &mut Vec<u8>, value: u8) {
+	buffer.push(value);
+}
 
+/* --------------------------------------------------------------------------------------------- */
+pub fn write_u16(buffer: &mut Vec<u8>, value: u16) {
+	buffer.extend_from_slice(&value.to_le_bytes());
+}
+pub fn write_u32(buffer: &mut Vec<u8>, value: u32) {
+	buffer.extend_from_slice(&value.to_le_bytes());
+}
+pub fn write_i32(buffer: &mut Vec<u8>, value: i32) {
+	buffer.extend_from_slice(&value.to_le_bytes());
+}
+pub fn write_u64(buffer: &mut Vec<u8>, value: u64) {
+	buffer.extend_from_slice(&value.to_le_bytes());
+}
+pub fn write_f32(buffer: &mut Vec<u8>, value: f32) {
+	buffer.extend_from_slice(&value.to_le_bytes());
+}
+pub fn write_f64(buffer: &mut Vec<u8>, value: f64) {
+	buffer.extend_from_slice(&value.to_le_bytes());
+}
+pub fn write_ptr(buffer: &mut Vec<u8>, pointer: (u16, u32)) {
+	write_u16(buffer, pointer.0);
+	write_u32(buffer, pointer.1);
+}
 
+/* --------------------------------------------------------------------------------------------- */
+// file header: the format version, the size of the types and test values to check the byte order
+pub fn write_file_header(buffer: &mut Vec<u8>) {
+
+	buffer.extend_from_slice(b"IGWD\0");
+	// data format version and minor version
+	write_one_byte(buffer, 8);
+	write_one_byte(buffer, 0);
+	// size of the types: INT_2, INT_4, INT_8, REAL_4, REAL_8
+	for size in [2, 4, 8, 4, 8] {
+		write_one_byte(buffer, size);
+	}
+	write_u16(buffer, 0x1234);
+	write_u32(buffer, 0x12345678);
+	write_u64(buffer, 0x0123456789abcdef);
+	write_f32(buffer, std::f32::consts::PI);
+	write_f64(buffer, std::f64::consts::PI);
+	// frame library (unknown) and check sum type (CRC)
+	write_one_byte(buffer, 0);
+	write_one_byte(buffer, 1);
+}
@@ -0,0 +1,66 @@
+/* --------------------------------------------------------------------------------------------- *
+ * Check sums of the frame files
+ * The frame format uses the CRC of the POSIX cksum command: polynomial 0x04C11DB7, most
+ * significant bit first, the length of the data being added after the data and the result
+ * being inverted.
+ * --------------------------------------------------------------------------------------------- */
+
+const POLYNOMIAL: u32 = 0x04c11db7;
+
+// table of the CRC of each byte value
+const TABLE: [u32; 256] = make_table();
+
+const fn make_table() -> [u32; 256] {
+	let mut table: [u32; 256] = [0; 256];
+	let mut i: usize = 0;
+	while i < 256 {
+		let mut crc: u32 = (i as u32) << 24;
+		let mut j: usize = 0;
+		while j < 8 {
+			crc = if crc & 0x80000000 != 0 { (crc << 1) ^ POLYNOMIAL } else { crc << 1 };
+			j += 1;
+		}
+		table[i] = crc;
+		i += 1;
+	}
+	table
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * define check sum
+ * --------------------------------------------------------------------------------------------- */
+// running check sum, updated as the bytes are read or written
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Crc {
+	crc: u32,
+	length: u64,
+}
+
+impl Crc {
+	pub fn new() -> Self {
+		Crc::default()
+	}
+	pub fn update(&mut self, bytes: &[u8]) {
+		for byte in bytes.iter() {
+			self.crc = (self.crc << 8) ^ TABLE[((self.crc >> 24) as u8 ^ byte) as usize];
+		}
+		self.length += bytes.len() as u64;
+	}
+	// check sum of all the bytes given so far
+	pub fn finish(&self) -> u32 {
+		let mut crc: u32 = self.crc;
+		let mut length: u64 = self.length;
+		while length != 0 {
+			crc = (crc << 8) ^ TABLE[((crc >> 24) as u8 ^ length as u8) as usize];
+			length >>= 8;
+		}
+		!crc
+	}
+}
+
+// check sum of a block of bytes
+pub fn checksum(bytes: &[u8]) -> u32 {
+	let mut crc = Crc::new();
+	crc.update(bytes);
+	crc.finish()
+}
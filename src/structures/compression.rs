@@ -0,0 +1,265 @@
+/* --------------------------------------------------------------------------------------------- *
+ * This file contains the compression of the data stored in the FrVect structures
+ * Compression codes (the 0x100 bit is set for little endian data, clear for big endian data):
+ *	-	0:	raw
+ *	-	1:	gzip (zlib stream)
+ *	-	3:	differentiation and gzip, for integer data
+ *	-	5:	differentiation and zero suppression, for 2 bytes words
+ *	-	8:	differentiation and zero suppression, for 4 bytes words
+ * The zero suppression stores the block size in the first word, then for each block the
+ * number of bits of its values (4 bits for 2 bytes words, 5 bits for 4 bytes words, 0 meaning a
+ * block of zeros) followed by the values offset to be positive, the bits being packed from the
+ * least significant one.
+ * --------------------------------------------------------------------------------------------- */
+
+use std::io::{
+	Read,
+	Write,
+};
+use flate2::{
+	Compression as Level,
+	read::ZlibDecoder,
+	write::ZlibEncoder,
+};
+
+// number of values in each block of the zero suppression
+const BLOCK_SIZE: usize = 8;
+
+/* --------------------------------------------------------------------------------------------- *
+ * define compression
+ * --------------------------------------------------------------------------------------------- */
+// compression applied to the vectors when writing, the gzip level going from 0 to 9; the
+// compressions not available for a data type fall back to gzip, and Auto picks the smallest
+// encoding of each vector
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Compression {
+	Raw,
+	Gzip(u32),
+	DiffGzip(u32),
+	ZeroSuppress,
+	Auto,
+}
+
+impl Compression {
+
+	// compress the little endian bytes of a vector, return the compression code and the bytes
+	pub fn compress(&self, data_type: u16, data: &[u8]) -> (u16, Vec<u8>) {
+		match self {
+			Self::Raw => (0x100, data.to_vec()),
+			Self::Gzip(level) => (0x101, gzip(data, *level)),
+			Self::DiffGzip(level) => match integer_size(data_type) {
+				Some(size) => (0x103, gzip(&differentiate(data, size), *level)),
+				None => (0x101, gzip(data, *level)),
+			},
+			Self::ZeroSuppress => match data_type {
+				1 | 9 => (0x105, zero_suppress(&differentiate(data, 2), 2)),
+				3 | 4 | 10 => (0x108, zero_suppress(&differentiate(data, 4), 4)),
+				_ => (0x101, gzip(data, 6)),
+			},
+			Self::Auto => [Self::Raw, Self::Gzip(6), Self::DiffGzip(6), Self::ZeroSuppress]
+				.iter().map(|x| x.compress(data_type, data))
+				.min_by_key(|(_, x)| x.len()).unwrap(),
+		}
+	}
+}
+
+// byte order of the data of a vector: the 0x100 bit of the compression code is set for little
+// endian data (LITTLEENDIAN_RAW) and clear for big endian data (BIGENDIAN_RAW)
+pub fn is_big_endian(compress: u16) -> bool {
+	(compress & 0x100) == 0
+}
+
+// restore the bytes of a vector, in the byte order given by the compression code
+pub fn expand(data_type: u16, compress: u16, data: &[u8], n_data: u64) -> Vec<u8> {
+
+	let big_endian: bool = is_big_endian(compress);
+	match compress & 0xff {
+		0 => data.to_vec(),
+		1 => gunzip(data),
+		3 => match integer_size(data_type) {
+			Some(size) => integrate(&gunzip(data), size, big_endian),
+			None => gunzip(data),
+		},
+		5 => integrate(&zero_expand(data, 2, n_data as usize, big_endian), 2, big_endian),
+		8 => integrate(&zero_expand(data, 4, n_data as usize, big_endian), 4, big_endian),
+		_ => panic!("Compression type not supported yet: {}", compress & 0xff),
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * helper functions
+ * --------------------------------------------------------------------------------------------- */
+// size of the words of the integer data types
+fn integer_size(data_type: u16) -> Option<usize> {
+	match data_type {
+		0 | 12 => Some(1),
+		1 | 9 => Some(2),
+		4 | 10 => Some(4),
+		5 | 11 => Some(8),
+		_ => None,
+	}
+}
+
+fn gzip(data: &[u8], level: u32) -> Vec<u8> {
+	let mut encoder = ZlibEncoder::new(Vec::new(), Level::new(level));
+	if let Err(e) = encoder.write_all(data) {
+		panic!("Can not compress the data: {}", e);
+	}
+	match encoder.finish() {
+		Ok(x) => x,
+		Err(e) => panic!("Can not compress the data: {}", e),
+	}
+}
+
+fn gunzip(data: &[u8]) -> Vec<u8> {
+	let mut output: Vec<u8> = Vec::new();
+	if let Err(e) = ZlibDecoder::new(data).read_to_end(&mut output) {
+		panic!("Can not expand the data: {}", e);
+	}
+	output
+}
+
+fn get_word(bytes: &[u8], big_endian: bool) -> u64 {
+	let mut word: [u8; 8] = [0; 8];
+	word[..bytes.len()].copy_from_slice(bytes);
+	if big_endian {
+		word[..bytes.len()].reverse();
+	}
+	u64::from_le_bytes(word)
+}
+
+fn put_word(output: &mut Vec<u8>, word: u64, size: usize, big_endian: bool) {
+	let bytes: [u8; 8] = word.to_le_bytes();
+	if big_endian {
+		output.extend(bytes[..size].iter().rev());
+	} else {
+		output.extend_from_slice(&bytes[..size]);
+	}
+}
+
+fn mask(n_bits: usize) -> u64 {
+	if n_bits >= 64 { u64::MAX } else { (1 << n_bits) - 1 }
+}
+
+// replace each little endian word by its difference with the previous one, the first word is
+// kept
+fn differentiate(data: &[u8], size: usize) -> Vec<u8> {
+	let mut output: Vec<u8> = Vec::with_capacity(data.len());
+	let mut previous: u64 = 0;
+	for bytes in data.chunks_exact(size) {
+		let word: u64 = get_word(bytes, false);
+		put_word(&mut output, word.wrapping_sub(previous), size, false);
+		previous = word;
+	}
+	output
+}
+
+// cumulative sum of the words, inverse of the differentiation
+fn integrate(data: &[u8], size: usize, big_endian: bool) -> Vec<u8> {
+	let mut output: Vec<u8> = Vec::with_capacity(data.len());
+	let mut previous: u64 = 0;
+	for bytes in data.chunks_exact(size) {
+		previous = previous.wrapping_add(get_word(bytes, big_endian)) & mask(8 * size);
+		put_word(&mut output, previous, size, big_endian);
+	}
+	output
+}
+
+// zero suppression of little endian words of 2 or 4 bytes
+fn zero_suppress(data: &[u8], size: usize) -> Vec<u8> {
+
+	let word_bits: usize = 8 * size;
+	let code_bits: usize = if size == 2 { 4 } else { 5 };
+	let mut output: Vec<u8> = Vec::new();
+	put_word(&mut output, BLOCK_SIZE as u64, size, false);
+
+	// bits packed from the least significant one
+	let mut stream: Vec<u8> = Vec::new();
+	let mut buffer: u128 = 0;
+	let mut n_buffer: usize = 0;
+	let mut push = |value: u64, n_bits: usize| {
+		buffer |= ((value & mask(n_bits)) as u128) << n_buffer;
+		n_buffer += n_bits;
+		while n_buffer >= 8 {
+			stream.push(buffer as u8);
+			buffer >>= 8;
+			n_buffer -= 8;
+		}
+	};
+	let words: Vec<u64> = data.chunks_exact(size).map(|x| get_word(x, false)).collect();
+	for block in words.chunks(BLOCK_SIZE) {
+		// signed values of the block
+		let values: Vec<i64> = block.iter()
+			.map(|x| ((x << (64 - word_bits)) as i64) >> (64 - word_bits)).collect();
+		if values.iter().all(|x| *x == 0) {
+			push(0, code_bits);
+			continue;
+		}
+		// the values stored on n bits go from -(2^(n-1) - 1) to 2^(n-1)
+		let n_bits: usize = (2..word_bits).find(|n| {
+			let w_max: i64 = (1 << (n - 1)) - 1;
+			values.iter().all(|x| (*x >= -w_max) & (*x <= w_max + 1))
+		}).unwrap_or(word_bits);
+		let w_max: u64 = (1 << (n_bits - 1)) - 1;
+		push((n_bits - 1) as u64, code_bits);
+		for value in block.iter() {
+			push(value.wrapping_add(w_max), n_bits);
+		}
+	}
+	if n_buffer > 0 {
+		stream.push(buffer as u8);
+	}
+	// pad the stream to a whole number of words
+	while !stream.len().is_multiple_of(size) {
+		stream.push(0);
+	}
+	output.append(&mut stream);
+	output
+}
+
+// expand n_data words of 2 or 4 bytes suppressed by zero_suppress
+fn zero_expand(data: &[u8], size: usize, n_data: usize, big_endian: bool) -> Vec<u8> {
+
+	let word_bits: usize = 8 * size;
+	let code_bits: usize = if size == 2 { 4 } else { 5 };
+	let block_size: usize = get_word(&data[0..size], big_endian) as usize;
+	// words turned into a stream of bits packed from the least significant one
+	let mut stream: Vec<u8> = Vec::new();
+	for bytes in data[size..].chunks_exact(size) {
+		put_word(&mut stream, get_word(bytes, big_endian), size, false);
+	}
+	let mut position: usize = 0;
+	let mut pull = |n_bits: usize| -> u64 {
+		let mut value: u64 = 0;
+		for i in 0..n_bits {
+			let bit: u8 = match stream.get((position + i) / 8) {
+				Some(x) => (x >> ((position + i) % 8)) & 1,
+				None => panic!("The zero suppressed data are too short."),
+			};
+			value |= (bit as u64) << i;
+		}
+		position += n_bits;
+		value
+	};
+
+	let mut output: Vec<u8> = Vec::with_capacity(n_data * size);
+	let mut i: usize = 0;
+	while i < n_data {
+		let code: usize = pull(code_bits) as usize;
+		let n: usize = block_size.min(n_data - i);
+		if code == 0 {
+			for _ in 0..n {
+				put_word(&mut output, 0, size, big_endian);
+			}
+		} else {
+			let n_bits: usize = code + 1;
+			let w_max: u64 = (1 << (n_bits - 1)) - 1;
+			for _ in 0..n {
+				let value: u64 = pull(n_bits).wrapping_sub(w_max) & mask(word_bits);
+				put_word(&mut output, value, size, big_endian);
+			}
+		}
+		i += n;
+	}
+	output
+}
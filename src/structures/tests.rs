@@ -0,0 +1,413 @@
+/* --------------------------------------------------------------------------------------------- *
+ * Tests of the frame structures
+ * --------------------------------------------------------------------------------------------- */
+
+use std::fs::File;
+use std::io::{
+	Bytes,
+	BufReader,
+	Read,
+};
+
+use num_complex::Complex;
+
+use crate::structures::{
+	*,
+	bytes::*,
+	vector::VectData,
+};
+use crate::series::*;
+use crate::test_util::TempFile;
+
+// write the bytes into a temporary file, return the file with an iterator on its bytes after the
+// file header
+fn read_back(name: &str, bytes: &[u8]) -> (TempFile, Bytes<BufReader<File>>) {
+	let file = TempFile::new(name);
+	std::fs::write(file.get_path(), bytes).unwrap();
+	let mut iterator = BufReader::new(File::open(file.get_path()).unwrap()).bytes();
+	for _i in 0..40 {
+		iterator.next();
+	}
+	(file, iterator)
+}
+
+#[test]
+fn end_of_file_version_8_layout() {
+	let mut bytes: Vec<u8> = Vec::new();
+	write_file_header(&mut bytes);
+	bytes.append(&mut write_structure(&FrEndOfFile::new(3, 86, 12), 0));
+	// check sum of the file
+	bytes.extend_from_slice(&[0; 4]);
+	assert_eq!(bytes.len(), 40 + 46);
+
+	let (_file, mut iterator) = read_back("end_of_file", &bytes);
+	match Structure::read(&mut iterator) {
+		Structure::FrEndOfFile(x) => {
+			assert_eq!(x.get_n_frames(), 3);
+			assert_eq!(x.get_n_bytes(), 86);
+			assert_eq!(x.get_seek_toc(), 12);
+		},
+		x => panic!("unexpected structure {:?}", x),
+	}
+	// the whole structure has been read
+	assert!(iterator.next().is_none());
+}
+
+// the dictionary of a structure type describes the bytes written for an instance: walking
+// through the elements uses exactly the bytes of the structure
+fn is_described<T: Reader + Writer>(structure: &T) -> bool {
+	let mut data: Vec<u8> = Vec::new();
+	structure.write(&mut data);
+	let (name, elements) = T::dictionary();
+	let unknown = FrUnknown {
+		length: 0,
+		instance: 0,
+		class: structure.class(),
+		name: name.to_string(),
+		elements: elements.iter().map(|(x, y)| (x.to_string(), y.to_string())).collect(),
+		data,
+	};
+	unknown.find_pointers().is_some()
+}
+
+#[test]
+fn dictionaries_match_written_bytes() {
+	let header = FrameH::new("TEST", 1, 0, 1000., 1.);
+	let adc = FrAdcData::new("H1:ADC", 0, 1, 16, 256.);
+	let detector = FrDetector::new("LIGO_HANFORD", "H1", -2.08, 0.81, 142.554);
+	let event = FrEvent::new("burst", 1000.5, 3.5);
+	let vect = FrVect::new("H1:ADC", VectData::I16(vec![1, 2, 3]), 1. / 256., 0., "s", "counts");
+	assert!(is_described(&FrSH::new("FrameH", 3, "")));
+	assert!(is_described(&FrSE::new("name", "STRING", "")));
+	assert!(is_described(&header));
+	assert!(is_described(&adc));
+	assert!(is_described(&detector));
+	assert!(is_described(&FrEndOfFile::new(1, 86, 12)));
+	assert!(is_described(&FrEndOfFrame::new(&header)));
+	assert!(is_described(&event));
+	assert!(is_described(&FrProcData::new("H1:STRAIN", 1, 0, 1.)));
+	assert!(is_described(&FrRawData::new("", (4, 0))));
+	assert!(is_described(&vect));
+
+	// table of contents with one entry of each index
+	let mut toc = FrTOC::new();
+	toc.add_frame(&header, 40, 200);
+	toc.add_adc(&adc, 200);
+	toc.add_proc(&FrProcData::new("H1:STRAIN", 1, 0, 1.), 300);
+	toc.add_detector(&detector, 400);
+	toc.add_event(&event, 500);
+	toc.set_dictionary(vec![(3, String::from("FrameH")), (4, String::from("FrAdcData"))]);
+	assert!(is_described(&toc));
+	assert!(is_described(&FrTOC::new()));
+}
+
+#[test]
+fn checksum_known_answer() {
+	// check value of the POSIX cksum CRC
+	assert_eq!(checksum::checksum(b"123456789"), 0x377a6011);
+	// the running check sum gives the same value as the check sum of the whole block
+	let mut crc = checksum::Crc::new();
+	crc.update(b"1234");
+	crc.update(b"56789");
+	assert_eq!(crc.finish(), 0x377a6011);
+}
+
+#[test]
+fn adc_counts_of_each_type() {
+	let adc = FrAdcData::new("H1:ADC", 0, 1, 16, 256.);
+	let vect = |values: VectData| FrVect::new("H1:ADC", values, 1. / 256., 0., "s", "counts");
+	assert_eq!(adc.get_counts(&vect(VectData::I16(vec![-3, 0, 7]))), Some(vec![-3, 0, 7]));
+	assert_eq!(adc.get_counts(&vect(VectData::U32(vec![1, 2]))), Some(vec![1, 2]));
+	// counts stored as real numbers are rounded
+	assert_eq!(adc.get_counts(&vect(VectData::F32(vec![-2.6, 0.4, 1.5]))),
+		Some(vec![-3, 0, 2]));
+	assert_eq!(adc.get_counts(&vect(VectData::F64(vec![32767.2, -32768.]))),
+		Some(vec![32767, -32768]));
+	assert_eq!(adc.get_counts(&vect(VectData::C16(vec![Complex::new(1., 0.)]))), None);
+}
+
+#[test]
+#[should_panic(expected = "(history, auxTable)")]
+fn frame_links_not_written_are_refused() {
+	let mut header = FrameH::new("TEST", 1, 0, 1000., 1.);
+	header.history = (9, 0);
+	header.aux_table = (18, 2);
+	header.set_links((12, 0), (0, 0), (0, 0), (0, 0));
+}
+
+#[test]
+#[should_panic(expected = "(table)")]
+fn detector_links_not_written_are_refused() {
+	let mut detector = FrDetector::new("LIGO_HANFORD", "H1", -2.08, 0.81, 142.554);
+	detector.table = (18, 0);
+	detector.set_links((0, 0));
+}
+
+#[test]
+fn cleared_links_are_written() {
+	let mut header = FrameH::new("TEST", 1, 0, 1000., 1.);
+	header.history = (9, 0);
+	header.sim_data = (14, 0);
+	header.clear_links();
+	header.set_links((12, 0), (0, 0), (5, 0), (0, 0));
+	assert_eq!(header.get_history(), (0, 0));
+	assert_eq!(header.get_sim_data(), (0, 0));
+	assert_eq!(header.get_raw_data(), (12, 0));
+	assert_eq!(header.get_detect_proc(), (5, 0));
+
+	let mut detector = FrDetector::new("LIGO_HANFORD", "H1", -2.08, 0.81, 142.554);
+	detector.aux = (20, 0);
+	detector.clear_links();
+	detector.set_links((5, 1));
+	assert_eq!(detector.get_aux(), (0, 0));
+	assert_eq!(detector.get_next(), (5, 1));
+}
+
+#[test]
+fn decode_byte_order_of_the_compression_flag() {
+	// gzip of the little endian words 1, -2, 300, -32768
+	let gzip: [u8; 16] = [0x78, 0x9c, 0x63, 0x64, 0xf8, 0xf7, 0x5f, 0x87, 0x91, 0xa1, 0x01, 0x00,
+		0x0c, 0x32, 0x02, 0xac];
+	assert_eq!(VectData::decode(1, 0x101, &gzip, 4), VectData::I16(vec![1, -2, 300, -32768]));
+	// the same words, raw, in both byte orders
+	let little: [u8; 8] = [0x01, 0x00, 0xfe, 0xff, 0x2c, 0x01, 0x00, 0x80];
+	let big: [u8; 8] = [0x00, 0x01, 0xff, 0xfe, 0x01, 0x2c, 0x80, 0x00];
+	assert_eq!(VectData::decode(1, 0x100, &little, 4), VectData::I16(vec![1, -2, 300, -32768]));
+	assert_eq!(VectData::decode(1, 0, &big, 4), VectData::I16(vec![1, -2, 300, -32768]));
+}
+
+#[test]
+fn vector_written_bytes() {
+	let mut vect = FrVect::new("V", VectData::I16(vec![1, -2]), 0.5, 0., "s", "");
+	let mut data: Vec<u8> = Vec::new();
+	vect.write(&mut data);
+	let expected: Vec<u8> = [
+		&[0x02, 0x00, b'V', 0x00][..],
+		// compress: raw little endian data
+		&[0x00, 0x01],
+		// type, nData, nBytes, data
+		&[0x01, 0x00],
+		&[0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+		&[0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+		&[0x01, 0x00, 0xfe, 0xff],
+		// nDim, nx, dx, startX
+		&[0x01, 0x00, 0x00, 0x00],
+		&[0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+		&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xe0, 0x3f],
+		&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+		// unitX, unitY, next
+		&[0x02, 0x00, b's', 0x00],
+		&[0x01, 0x00, 0x00],
+		&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+	].concat();
+	assert_eq!(data, expected);
+
+	// the raw compression keeps the bytes and the byte order flag
+	vect.compress(&compression::Compression::Raw);
+	assert_eq!(vect.get_compress(), 0x100);
+	assert_eq!(vect.get_data(), vec![0x01, 0x00, 0xfe, 0xff]);
+}
+
+#[test]
+fn proc_aux_params_in_file_order() {
+	let mut proc = FrProcData::new("H1:STRAIN", 1, 0, 1.);
+	proc.set_aux_param("window", 2.);
+	proc.set_aux_param("alpha", 0.5);
+	// a repeated name, as written by other frame libraries
+	proc.aux_param.push((String::from("window"), 3.));
+	proc.set_aux_param("alpha", 0.25);
+	let mut bytes: Vec<u8> = Vec::new();
+	write_file_header(&mut bytes);
+	bytes.append(&mut write_structure(&proc, 0));
+
+	let (_file, mut iterator) = read_back("aux_params", &bytes);
+	match Structure::read(&mut iterator) {
+		Structure::FrProcData(x) => {
+			assert_eq!(x.get_aux_param(), vec![(String::from("window"), 2.),
+				(String::from("alpha"), 0.25), (String::from("window"), 3.)]);
+			assert_eq!(x.get_aux_param_value("window"), Some(2.));
+			assert_eq!(x.get_aux_param_value("beta"), None);
+		},
+		x => panic!("unexpected structure {:?}", x),
+	}
+}
+
+#[test]
+#[should_panic(expected = "The event index of the table of contents counts 3 instances but holds 1.")]
+fn event_index_shorter_than_the_counts() {
+	let mut toc = FrTOC::new();
+	toc.add_event(&FrEvent::new("burst", 1000.5, 3.5), 500);
+	// the counts of the file give more events than the arrays hold
+	toc.n_event[0] = 3;
+	toc.get_event_index();
+}
+
+#[test]
+#[should_panic(expected = "The static data index of the table of contents counts 2 instances but holds 0.")]
+fn stat_index_shorter_than_the_counts() {
+	let mut toc = FrTOC::new();
+	toc.name_stat.push(String::from("CALIBRATION"));
+	toc.detector_stat.push(String::from("LIGO_HANFORD"));
+	toc.n_stat.push(2);
+	toc.get_stat_index();
+}
+
+#[test]
+fn decode_types_and_axes() {
+	// words of several types, in both byte orders
+	assert_eq!(VectData::decode(2, 0, &1.5f64.to_be_bytes(), 1), VectData::F64(vec![1.5]));
+	assert_eq!(VectData::decode(12, 0x100, &[7, 255], 2), VectData::U8(vec![7, 255]));
+	let complex: Vec<u8> = [2f32, -1.].iter().flat_map(|x| x.to_le_bytes()).collect();
+	assert_eq!(VectData::decode(6, 0x100, &complex, 1), VectData::C8(vec![Complex::new(2., -1.)]));
+	// frame strings: length with the null character, characters, null character
+	let strings: [u8; 8] = [0x03, 0x00, b'a', b'b', 0x00, 0x01, 0x00, 0x00];
+	assert_eq!(VectData::decode(8, 0x100, &strings, 2),
+		VectData::Str(vec![String::from("ab"), String::new()]));
+
+	// two dimensional vector: 2 times of 3 frequencies
+	let mut vect = FrVect::new("TF", VectData::F64((0..6).map(f64::from).collect()), 0.5, 1., "s",
+		"");
+	vect.n_dim = 2;
+	vect.dim = vec![2, 3];
+	vect.dx = vec![0.5, 10.];
+	vect.start_x = vec![1., 100.];
+	vect.unit_x = vec![String::from("s"), String::from("Hz")];
+	assert_eq!(vect.get_shape(), vec![2, 3]);
+	let axes = vect.get_axes();
+	assert_eq!(axes[0].get_coordinates(), vec![1., 1.5]);
+	assert_eq!(axes[1].get_coordinates(), vec![100., 110., 120.]);
+	assert_eq!(axes[1].unit, crate::units::Unit::parse("Hz"));
+}
+
+#[test]
+fn heterodyne_restored() {
+	let close = |x: &[Complex<f64>], y: &[Complex<f64>]| (x.len() == y.len())
+		& x.iter().zip(y).all(|(a, b)| (a - b).norm() < 1e-12);
+	let ones: Vec<Complex<f64>> = vec![Complex::new(1., 0.); 4];
+	// a quarter of a turn per sample
+	let quarter: Vec<Complex<f64>> = vec![Complex::new(1., 0.), Complex::new(0., 1.),
+		Complex::new(-1., 0.), Complex::new(0., -1.)];
+	assert!(close(&vector::restore_heterodyne(&ones, 0.25, 1., 0.), &quarter));
+	// the phase turns every sample
+	let turned: Vec<Complex<f64>> = quarter.iter().map(|x| x * Complex::new(0., 1.)).collect();
+	assert!(close(&vector::restore_heterodyne(&ones, 0.25, 1., std::f64::consts::FRAC_PI_2),
+		&turned));
+
+	// processed data, the sampling step being given by the first axis of the vector
+	let mut proc = FrProcData::new("H1:BASEBAND", 1, 0, 1.);
+	proc.set_heterodyne(1., 0.);
+	let vect = FrVect::new("H1:BASEBAND", VectData::C16(ones), 0.25, 0., "s", "");
+	assert!(close(&proc.restore_band(&vect), &quarter));
+}
+
+fn sim_event(instance: u32, name: &str, gps_sec: u32, amplitude: f32) -> Vec<u8> {
+	let mut body: Vec<u8> = Vec::new();
+	for string in [name, "", ""] {
+		write_one_string(&mut body, string);
+	}
+	for value in [gps_sec, 500_000_000] {
+		write_u32(&mut body, value);
+	}
+	for value in [0.1, 0.2, amplitude] {
+		write_f32(&mut body, value);
+	}
+	write_u16(&mut body, 1);
+	write_f64(&mut body, 30.);
+	write_one_string(&mut body, "mass");
+	for pointer in [(0, 0), (0, 0), (0, 0)] {
+		write_ptr(&mut body, pointer);
+	}
+	write_body(15, instance, body)
+}
+
+#[test]
+fn sim_events_found_from_the_table_of_contents() {
+	let header = FrameH::new("TEST", 1, 0, 1000., 10.);
+	let mut bytes: Vec<u8> = Vec::new();
+	write_file_header(&mut bytes);
+	bytes.append(&mut write_structure(&header, 0));
+	let mut toc = FrTOC::new();
+	toc.name_sim_event = vec![String::from("burst"), String::from("inspiral")];
+	toc.n_sim_event = vec![2, 1];
+	// the events of the index are sorted by type
+	for (instance, (name, gps_sec, amplitude)) in [("burst", 1001, 2.), ("burst", 1007, 4.),
+		("inspiral", 1003, 8.)].into_iter().enumerate() {
+		toc.gps_sec_sim_event.push(gps_sec);
+		toc.gps_nano_sim_event.push(500_000_000);
+		toc.amplitude_sim_event.push(amplitude);
+		toc.position_sim_event.push(bytes.len() as u64);
+		bytes.append(&mut sim_event(instance as u32, name, gps_sec, amplitude));
+	}
+	bytes.append(&mut write_structure(&FrEndOfFrame::new(&header), 0));
+	let mut toc_bytes: Vec<u8> = write_structure(&toc, 0);
+	let seek_toc: u64 = toc_bytes.len() as u64 + 46;
+	bytes.append(&mut toc_bytes);
+	let n_bytes: u64 = bytes.len() as u64 + 46;
+	bytes.append(&mut write_structure(&FrEndOfFile::new(1, n_bytes, seek_toc), 0));
+	bytes.extend_from_slice(&[0; 4]);
+	let file = TempFile::new("sim_events");
+	std::fs::write(file.get_path(), bytes).unwrap();
+
+	let amplitudes = |gps_start: f64, gps_end: f64| crate::find_sim_events(file.get_path(),
+		gps_start, gps_end).iter().map(|x| x.get_amplitude()).collect::<Vec<f32>>();
+	assert_eq!(amplitudes(0., 1e10), vec![2., 4., 8.]);
+	assert_eq!(amplitudes(1002., 1005.), vec![8.]);
+	assert_eq!(amplitudes(1008., 1e10), Vec::<f32>::new());
+	let events: Vec<FrSimEvent> = crate::find_sim_events(file.get_path(), 1000., 1002.);
+	assert_eq!(events[0].get_name(), "burst");
+	assert_eq!(events[0].get_time(), 1001.5);
+	assert_eq!(events[0].get_param(), vec![(String::from("mass"), 30.)]);
+}
+
+#[test]
+fn processed_data_decoded_by_type() {
+	let vect = FrVect::new("H1:PROC", VectData::F64(vec![1., 2., 3., 4.]), 0.25, 0.5, "s",
+		"strain");
+
+	// time series, shifted by the time offset of the processed data
+	let mut proc = FrProcData::new("H1:PROC", 1, 0, 1.);
+	proc.set_time_offset(2.);
+	match proc.decode(&vect) {
+		ProcSeries::TimeSeries(x) => {
+			assert_eq!(x.values, VectData::F64(vec![1., 2., 3., 4.]));
+			assert_eq!(x.unit, crate::units::Unit::parse("strain"));
+			assert_eq!(x.time.start, 2.5);
+			assert_eq!(x.time.dx, 0.25);
+		},
+		x => panic!("unexpected series {:?}", x),
+	}
+
+	// power spectral density
+	let mut proc = FrProcData::new("H1:PROC", 2, 3, 1.);
+	proc.set_frequency(1024., 0.25);
+	match proc.decode(&vect) {
+		ProcSeries::FrequencySeries(x) => {
+			assert_eq!(x.kind, SpectrumType::PowerSpectralDensity);
+			assert_eq!(x.frequency.get_coordinates(), vec![0.5, 0.75, 1., 1.25]);
+			assert_eq!((x.f_range, x.bw), (1024., 0.25));
+		},
+		x => panic!("unexpected series {:?}", x),
+	}
+
+	// time-frequency map: 2 times of 2 frequencies
+	let mut map = FrVect::new("H1:PROC", VectData::F64(vec![1., 2., 3., 4.]), 0.5, 0., "s", "");
+	map.n_dim = 2;
+	map.dim = vec![2, 2];
+	map.dx = vec![0.5, 10.];
+	map.start_x = vec![0., 100.];
+	map.unit_x = vec![String::from("s"), String::from("Hz")];
+	let mut proc = FrProcData::new("H1:PROC", 4, 0, 1.);
+	proc.set_time_offset(1.);
+	match proc.decode(&map) {
+		ProcSeries::TimeFrequency(x) => {
+			assert_eq!(x.get_shape(), (2, 2));
+			assert_eq!(x.get_times(), vec![1., 1.5]);
+			assert_eq!(x.get_frequencies(), vec![100., 110.]);
+		},
+		x => panic!("unexpected series {:?}", x),
+	}
+
+	// a time series can not be made of a two dimensional vector
+	let proc = FrProcData::new("H1:PROC", 1, 0, 1.);
+	assert_eq!(proc.decode(&map), ProcSeries::Other(VectData::F64(vec![1., 2., 3., 4.])));
+}
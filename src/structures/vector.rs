@@ -0,0 +1,304 @@
+/* --------------------------------------------------------------------------------------------- *
+ * This file contains the decoding of the data stored in the FrVect structures
+ * Data types:
+ *	-	0:	FR_VECT_C	(i8)
+ *	-	1:	FR_VECT_2S	(i16)
+ *	-	2:	FR_VECT_8R	(f64)
+ *	-	3:	FR_VECT_4R	(f32)
+ *	-	4:	FR_VECT_4S	(i32)
+ *	-	5:	FR_VECT_8S	(i64)
+ *	-	6:	FR_VECT_8C	(complex f32)
+ *	-	7:	FR_VECT_16C	(complex f64)
+ *	-	8:	FR_VECT_STRING
+ *	-	9:	FR_VECT_2U	(u16)
+ *	-	10:	FR_VECT_4U	(u32)
+ *	-	11:	FR_VECT_8U	(u64)
+ *	-	12:	FR_VECT_1U	(u8)
+ * --------------------------------------------------------------------------------------------- */
+
+use std::str;
+use std::f64::consts::PI;
+use num_complex::Complex;
+
+use crate::units::Unit;
+use crate::structures::compression::{
+	expand,
+	is_big_endian,
+};
+
+/* --------------------------------------------------------------------------------------------- *
+ * define enum of data
+ * --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug, Clone)]
+pub enum VectData {
+	I8(Vec<i8>),
+	I16(Vec<i16>),
+	F64(Vec<f64>),
+	F32(Vec<f32>),
+	I32(Vec<i32>),
+	I64(Vec<i64>),
+	C8(Vec<Complex<f32>>),
+	C16(Vec<Complex<f64>>),
+	Str(Vec<String>),
+	U16(Vec<u16>),
+	U32(Vec<u32>),
+	U64(Vec<u64>),
+	U8(Vec<u8>),
+}
+
+impl VectData {
+
+	// decode the bytes of a FrVect
+	pub fn decode(data_type: u16, compress: u16, data: &[u8], n_data: u64) -> Self {
+
+		let big_endian: bool = is_big_endian(compress);
+		let bytes: Vec<u8> = expand(data_type, compress, data, n_data);
+
+		let n_data = n_data as usize;
+		let output: VectData = match data_type {
+			0 => Self::I8(bytes.iter().take(n_data).map(|x| *x as i8).collect()),
+			1 => Self::I16(split::<2>(&bytes, n_data, big_endian).into_iter()
+				.map(i16::from_le_bytes).collect()),
+			2 => Self::F64(split::<8>(&bytes, n_data, big_endian).into_iter()
+				.map(f64::from_le_bytes).collect()),
+			3 => Self::F32(split::<4>(&bytes, n_data, big_endian).into_iter()
+				.map(f32::from_le_bytes).collect()),
+			4 => Self::I32(split::<4>(&bytes, n_data, big_endian).into_iter()
+				.map(i32::from_le_bytes).collect()),
+			5 => Self::I64(split::<8>(&bytes, n_data, big_endian).into_iter()
+				.map(i64::from_le_bytes).collect()),
+			6 => Self::C8(split::<4>(&bytes, 2 * n_data, big_endian).chunks_exact(2)
+				.map(|x| Complex::new(f32::from_le_bytes(x[0]), f32::from_le_bytes(x[1])))
+				.collect()),
+			7 => Self::C16(split::<8>(&bytes, 2 * n_data, big_endian).chunks_exact(2)
+				.map(|x| Complex::new(f64::from_le_bytes(x[0]), f64::from_le_bytes(x[1])))
+				.collect()),
+			8 => Self::Str(split_strings(&bytes, n_data, big_endian)),
+			9 => Self::U16(split::<2>(&bytes, n_data, big_endian).into_iter()
+				.map(u16::from_le_bytes).collect()),
+			10 => Self::U32(split::<4>(&bytes, n_data, big_endian).into_iter()
+				.map(u32::from_le_bytes).collect()),
+			11 => Self::U64(split::<8>(&bytes, n_data, big_endian).into_iter()
+				.map(u64::from_le_bytes).collect()),
+			12 => Self::U8(bytes.iter().take(n_data).copied().collect()),
+			_ => panic!("Data type not supported yet: {}", data_type),
+		};
+		output
+	}
+
+	// encode the data into little endian bytes, return the data type and the bytes
+	pub fn encode(&self) -> (u16, Vec<u8>) {
+		match self {
+			Self::I8(ref x) => (0, x.iter().map(|v| *v as u8).collect()),
+			Self::I16(ref x) => (1, x.iter().flat_map(|v| v.to_le_bytes()).collect()),
+			Self::F64(ref x) => (2, x.iter().flat_map(|v| v.to_le_bytes()).collect()),
+			Self::F32(ref x) => (3, x.iter().flat_map(|v| v.to_le_bytes()).collect()),
+			Self::I32(ref x) => (4, x.iter().flat_map(|v| v.to_le_bytes()).collect()),
+			Self::I64(ref x) => (5, x.iter().flat_map(|v| v.to_le_bytes()).collect()),
+			Self::C8(ref x) => (6, x.iter()
+				.flat_map(|v| [v.re.to_le_bytes(), v.im.to_le_bytes()].concat()).collect()),
+			Self::C16(ref x) => (7, x.iter()
+				.flat_map(|v| [v.re.to_le_bytes(), v.im.to_le_bytes()].concat()).collect()),
+			Self::Str(ref x) => (8, x.iter().flat_map(|v| {
+				// frame strings: length with the null character, characters, null character
+				let mut word: Vec<u8> = ((v.len() + 1) as u16).to_le_bytes().to_vec();
+				word.extend_from_slice(v.as_bytes());
+				word.push(0);
+				word
+			}).collect()),
+			Self::U16(ref x) => (9, x.iter().flat_map(|v| v.to_le_bytes()).collect()),
+			Self::U32(ref x) => (10, x.iter().flat_map(|v| v.to_le_bytes()).collect()),
+			Self::U64(ref x) => (11, x.iter().flat_map(|v| v.to_le_bytes()).collect()),
+			Self::U8(ref x) => (12, x.clone()),
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		match self {
+			Self::I8(ref x) => x.len(),
+			Self::I16(ref x) => x.len(),
+			Self::F64(ref x) => x.len(),
+			Self::F32(ref x) => x.len(),
+			Self::I32(ref x) => x.len(),
+			Self::I64(ref x) => x.len(),
+			Self::C8(ref x) => x.len(),
+			Self::C16(ref x) => x.len(),
+			Self::Str(ref x) => x.len(),
+			Self::U16(ref x) => x.len(),
+			Self::U32(ref x) => x.len(),
+			Self::U64(ref x) => x.len(),
+			Self::U8(ref x) => x.len(),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	// append the data of another vector of the same type
+	pub fn append(&mut self, other: Self) {
+		match (self, other) {
+			(Self::I8(ref mut x), Self::I8(mut y)) => x.append(&mut y),
+			(Self::I16(ref mut x), Self::I16(mut y)) => x.append(&mut y),
+			(Self::F64(ref mut x), Self::F64(mut y)) => x.append(&mut y),
+			(Self::F32(ref mut x), Self::F32(mut y)) => x.append(&mut y),
+			(Self::I32(ref mut x), Self::I32(mut y)) => x.append(&mut y),
+			(Self::I64(ref mut x), Self::I64(mut y)) => x.append(&mut y),
+			(Self::C8(ref mut x), Self::C8(mut y)) => x.append(&mut y),
+			(Self::C16(ref mut x), Self::C16(mut y)) => x.append(&mut y),
+			(Self::Str(ref mut x), Self::Str(mut y)) => x.append(&mut y),
+			(Self::U16(ref mut x), Self::U16(mut y)) => x.append(&mut y),
+			(Self::U32(ref mut x), Self::U32(mut y)) => x.append(&mut y),
+			(Self::U64(ref mut x), Self::U64(mut y)) => x.append(&mut y),
+			(Self::U8(ref mut x), Self::U8(mut y)) => x.append(&mut y),
+			_ => panic!("Can not append data of different types."),
+		}
+	}
+
+	// truncate the vector to n values, or pad it with zeros (empty strings)
+	pub fn resize(&mut self, n: usize) {
+		match self {
+			Self::I8(ref mut x) => x.resize(n, 0),
+			Self::I16(ref mut x) => x.resize(n, 0),
+			Self::F64(ref mut x) => x.resize(n, 0.),
+			Self::F32(ref mut x) => x.resize(n, 0.),
+			Self::I32(ref mut x) => x.resize(n, 0),
+			Self::I64(ref mut x) => x.resize(n, 0),
+			Self::C8(ref mut x) => x.resize(n, Complex::new(0., 0.)),
+			Self::C16(ref mut x) => x.resize(n, Complex::new(0., 0.)),
+			Self::Str(ref mut x) => x.resize(n, String::new()),
+			Self::U16(ref mut x) => x.resize(n, 0),
+			Self::U32(ref mut x) => x.resize(n, 0),
+			Self::U64(ref mut x) => x.resize(n, 0),
+			Self::U8(ref mut x) => x.resize(n, 0),
+		}
+	}
+
+	// split the vector at index at, return the values from at
+	pub fn split_off(&mut self, at: usize) -> Self {
+		match self {
+			Self::I8(ref mut x) => Self::I8(x.split_off(at)),
+			Self::I16(ref mut x) => Self::I16(x.split_off(at)),
+			Self::F64(ref mut x) => Self::F64(x.split_off(at)),
+			Self::F32(ref mut x) => Self::F32(x.split_off(at)),
+			Self::I32(ref mut x) => Self::I32(x.split_off(at)),
+			Self::I64(ref mut x) => Self::I64(x.split_off(at)),
+			Self::C8(ref mut x) => Self::C8(x.split_off(at)),
+			Self::C16(ref mut x) => Self::C16(x.split_off(at)),
+			Self::Str(ref mut x) => Self::Str(x.split_off(at)),
+			Self::U16(ref mut x) => Self::U16(x.split_off(at)),
+			Self::U32(ref mut x) => Self::U32(x.split_off(at)),
+			Self::U64(ref mut x) => Self::U64(x.split_off(at)),
+			Self::U8(ref mut x) => Self::U8(x.split_off(at)),
+		}
+	}
+
+	// cast the numerical data into f64
+	pub fn to_f64(&self) -> Vec<f64> {
+		match self {
+			Self::I8(ref x) => x.iter().map(|v| *v as f64).collect(),
+			Self::I16(ref x) => x.iter().map(|v| *v as f64).collect(),
+			Self::F64(ref x) => x.clone(),
+			Self::F32(ref x) => x.iter().map(|v| *v as f64).collect(),
+			Self::I32(ref x) => x.iter().map(|v| *v as f64).collect(),
+			Self::I64(ref x) => x.iter().map(|v| *v as f64).collect(),
+			Self::C8(_) | Self::C16(_) => panic!("Complex data can not be cast into f64."),
+			Self::Str(_) => panic!("String data can not be cast into f64."),
+			Self::U16(ref x) => x.iter().map(|v| *v as f64).collect(),
+			Self::U32(ref x) => x.iter().map(|v| *v as f64).collect(),
+			Self::U64(ref x) => x.iter().map(|v| *v as f64).collect(),
+			Self::U8(ref x) => x.iter().map(|v| *v as f64).collect(),
+		}
+	}
+
+	// cast the integer data into i64
+	pub fn to_i64(&self) -> Vec<i64> {
+		match self {
+			Self::I8(ref x) => x.iter().map(|v| *v as i64).collect(),
+			Self::I16(ref x) => x.iter().map(|v| *v as i64).collect(),
+			Self::I32(ref x) => x.iter().map(|v| *v as i64).collect(),
+			Self::I64(ref x) => x.clone(),
+			Self::U16(ref x) => x.iter().map(|v| *v as i64).collect(),
+			Self::U32(ref x) => x.iter().map(|v| *v as i64).collect(),
+			Self::U64(ref x) => x.iter().map(|v| *v as i64).collect(),
+			Self::U8(ref x) => x.iter().map(|v| *v as i64).collect(),
+			_ => panic!("Only integer data can be cast into i64."),
+		}
+	}
+
+	// cast the numerical data into complex f64, real data get a null imaginary part
+	pub fn to_complex(&self) -> Vec<Complex<f64>> {
+		match self {
+			Self::C8(ref x) => x.iter().map(|v| Complex::new(v.re as f64, v.im as f64)).collect(),
+			Self::C16(ref x) => x.clone(),
+			_ => self.to_f64().into_iter().map(|v| Complex::new(v, 0.)).collect(),
+		}
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * define axis of a multi-dimensional vector
+ * --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug, Clone)]
+pub struct Axis {
+	pub n: u64,
+	pub dx: f64,
+	pub start: f64,
+	pub unit: Unit,
+}
+
+impl Axis {
+	// coordinate of each sample along the axis: start + i * dx
+	pub fn get_coordinates(&self) -> Vec<f64> {
+		(0..self.n).map(|i| self.start + (i as f64) * self.dx).collect()
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * heterodyne reconstruction
+ * --------------------------------------------------------------------------------------------- */
+// The data heterodyned at the frequency f_shift are brought back to the original band:
+// x(t) = y(t) * exp(i * (2 pi f_shift t + phase)), t being the time since the first sample
+pub fn restore_heterodyne(values: &[Complex<f64>], dt: f64, f_shift: f64, phase: f64)
+	-> Vec<Complex<f64>> {
+
+	values.iter().enumerate().map(|(i, x)| {
+		let angle: f64 = 2. * PI * f_shift * (i as f64) * dt + phase;
+		x * Complex::from_polar(1., angle)
+	}).collect()
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * split functions
+ * --------------------------------------------------------------------------------------------- */
+// cut the byte vector into little endian words of N bytes
+fn split<const N: usize>(bytes: &[u8], n_data: usize, big_endian: bool) -> Vec<[u8; N]> {
+
+	assert!(bytes.len() >= n_data * N, "Not enough bytes to decode {} words of {} bytes.",
+		n_data, N);
+	bytes.chunks_exact(N).take(n_data).map(|chunk| {
+		let mut word: [u8; N] = chunk.try_into().unwrap();
+		if big_endian { word.reverse(); }
+		word
+	}).collect()
+}
+
+// read a list of frame strings (u16 length followed by the characters)
+fn split_strings(bytes: &[u8], n_data: usize, big_endian: bool) -> Vec<String> {
+
+	let mut output: Vec<String> = Vec::new();
+	let mut position: usize = 0;
+	for _i in 0..n_data {
+		let mut word: [u8; 2] = bytes[position..position + 2].try_into().unwrap();
+		if big_endian { word.reverse(); }
+		let length = u16::from_le_bytes(word) as usize;
+		position += 2;
+		let s = match str::from_utf8(&bytes[position..position + length]) {
+			Ok(v) => v,
+			Err(e) => panic!("Invalid UTF-8 sequence: {}", e),
+		};
+		output.push(s.trim_end_matches('\0').to_string());
+		position += length;
+	}
+	output
+}
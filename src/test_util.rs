@@ -0,0 +1,34 @@
+/* --------------------------------------------------------------------------------------------- *
+ * Helpers shared by the tests
+ * --------------------------------------------------------------------------------------------- */
+
+// temporary file used by a test, removed when the guard is dropped, including when the test
+// panics
+pub struct TempFile {
+	path: String,
+}
+
+impl TempFile {
+
+	// file of the temporary directory, unique to the test and to the process
+	pub fn new(name: &str) -> Self {
+		TempFile::at(&std::env::temp_dir()
+			.join(format!("rustframe_{}_{}.gwf", name, std::process::id())).to_string_lossy())
+	}
+	// file at a path given by the code under test
+	pub fn at(path: &str) -> Self {
+		TempFile {
+			path: path.to_string(),
+		}
+	}
+	pub fn get_path(&self) -> &str {
+		&self.path
+	}
+}
+
+impl Drop for TempFile {
+	fn drop(&mut self) {
+		// the file is not written if the test fails before
+		let _ = std::fs::remove_file(&self.path);
+	}
+}
@@ -0,0 +1,188 @@
+/* --------------------------------------------------------------------------------------------- *
+ * Tests of the frame readers, on files built structure by structure
+ * --------------------------------------------------------------------------------------------- */
+
+use crate::*;
+use crate::test_util::TempFile;
+
+// write a file of one frame made of the header and the given structures
+fn write_frame_file(path: &str, header: &FrameH, structures: Vec<Vec<u8>>) {
+	let mut bytes: Vec<u8> = Vec::new();
+	write_file_header(&mut bytes);
+	bytes.append(&mut write_structure(header, 0));
+	for mut structure in structures {
+		bytes.append(&mut structure);
+	}
+	bytes.append(&mut write_structure(&FrEndOfFrame::new(header), 0));
+	let n_bytes: u64 = bytes.len() as u64 + 46;
+	bytes.append(&mut write_structure(&FrEndOfFile::new(1, n_bytes, 0), 0));
+	bytes.extend_from_slice(&[0; 4]);
+	std::fs::write(path, bytes).unwrap();
+}
+
+fn raw_data(first_ser: (u16, u32)) -> Vec<u8> {
+	let mut body: Vec<u8> = Vec::new();
+	write_one_string(&mut body, "");
+	for pointer in [first_ser, (0, 0), (0, 0), (0, 0), (0, 0)] {
+		write_ptr(&mut body, pointer);
+	}
+	write_body(12, 0, body)
+}
+
+fn serial_data(instance: u32, gps: f64, sample_rate: f64, data: &str, next: (u16, u32))
+	-> Vec<u8> {
+	let mut body: Vec<u8> = Vec::new();
+	write_one_string(&mut body, "SERIAL");
+	write_u32(&mut body, gps.floor() as u32);
+	write_u32(&mut body, ((gps - gps.floor()) * 1e9).round() as u32);
+	write_f64(&mut body, sample_rate);
+	write_one_string(&mut body, data);
+	for pointer in [(0, 0), (0, 0), next] {
+		write_ptr(&mut body, pointer);
+	}
+	write_body(13, instance, body)
+}
+
+#[test]
+fn serial_channel_over_the_chain() {
+	let file = TempFile::new("serial_channel");
+	let path: &str = file.get_path();
+	let mut header = FrameH::new("TEST", 1, 0, 1000., 4.);
+	header.set_links((12, 0), (0, 0), (0, 0), (0, 0));
+	write_frame_file(path, &header, vec![
+		raw_data((13, 0)),
+		serial_data(0, 1000.5, 2., "TEMP 21.5 PRESSURE 1.2 TEMP 21.6", (13, 1)),
+		serial_data(1, 1002.5, 2., "PRESSURE 1.3", (13, 2)),
+		serial_data(2, 1003., 2., "TEMP 21.7", (0, 0)),
+	]);
+	let frames: Vec<Frame> = read_file(path, "TEMP", 0., 1e10);
+	assert_eq!(frames[0].channels.len(), 1);
+	let channel: &Channel = &frames[0].channels[0];
+	assert_eq!(channel.get_name(), "TEMP");
+	assert_eq!(channel.get_values(), Some(VectData::F64(vec![21.5, 21.6, 21.7])));
+	// each vector starts at the time of its structure in the frame
+	let starts: Vec<f64> = channel.vect.iter().map(|x| x.get_axes()[0].start).collect();
+	assert_eq!(starts, vec![0.5, 3.]);
+	assert_eq!(channel.vect[0].get_axes()[0].dx, 0.5);
+}
+
+fn static_data(instance: u32, detector: (u16, u32), version: u32, data: (u16, u32)) -> Vec<u8> {
+	let mut body: Vec<u8> = Vec::new();
+	for string in ["CALIBRATION", "", ""] {
+		write_one_string(&mut body, string);
+	}
+	for value in [900, 0, version] {
+		write_u32(&mut body, value);
+	}
+	for pointer in [detector, data, (0, 0)] {
+		write_ptr(&mut body, pointer);
+	}
+	write_body(16, instance, body)
+}
+
+#[test]
+fn static_data_of_a_detector() {
+	let file = TempFile::new("static_data");
+	let path: &str = file.get_path();
+	let mut header = FrameH::new("TEST", 1, 0, 1000., 1.);
+	header.set_links((0, 0), (0, 0), (5, 0), (0, 0));
+	let mut hanford = FrDetector::new("LIGO_HANFORD", "H1", -2.08, 0.81, 142.554);
+	hanford.set_links((5, 1));
+	let livingston = FrDetector::new("LIGO_LIVINGSTON", "L1", -1.58, 0.53, -6.574);
+	let vect = |instance: u32, value: f64| write_structure(
+		&FrVect::new("CALIBRATION", VectData::F64(vec![value]), 1., 0., "", ""), instance);
+	write_frame_file(path, &header, vec![
+		write_structure(&hanford, 0),
+		write_structure(&livingston, 1),
+		// the Livingston data have the highest version
+		static_data(0, (5, 0), 1, (20, 0)),
+		static_data(1, (5, 1), 2, (20, 1)),
+		vect(0, 1.),
+		vect(1, 2.),
+	]);
+	let frames: Vec<Frame> = read_file(path, "", 0., 1e10);
+	let frame: &Frame = &frames[0];
+	let values = |detector: &str| frame.get_static_data("CALIBRATION", detector, 1000.)
+		.and_then(|x| x.get_values());
+	assert_eq!(values("LIGO_HANFORD"), Some(VectData::F64(vec![1.])));
+	assert_eq!(values("LIGO_LIVINGSTON"), Some(VectData::F64(vec![2.])));
+	assert_eq!(values("VIRGO"), None);
+}
+
+#[test]
+fn chains_with_missing_structures() {
+	let file = TempFile::new("missing_structures");
+	let path: &str = file.get_path();
+	let mut header = FrameH::new("TEST", 1, 0, 1000., 1.);
+	header.set_links((12, 0), (0, 0), (0, 0), (0, 0));
+	// the second ADC channel and the second vector of the chain are missing
+	let mut adc = FrAdcData::new("H1:ADC", 0, 1, 16, 4.);
+	adc.set_links((20, 0), (0, 0), (4, 9));
+	let mut vect = FrVect::new("H1:ADC", VectData::I16(vec![1, 2, 3, 4]), 0.25, 0., "s", "");
+	vect.set_next((20, 7));
+	write_frame_file(path, &header, vec![
+		write_structure(&FrRawData::new("", (4, 0)), 0),
+		write_structure(&adc, 0),
+		write_structure(&vect, 0),
+	]);
+	let frames: Vec<Frame> = read_file(path, "H1:ADC", 0., 1e10);
+	assert_eq!(frames[0].channels.len(), 1);
+	assert_eq!(frames[0].channels[0].vect.len(), 1);
+	assert_eq!(frames[0].channels[0].get_values(), Some(VectData::I16(vec![1, 2, 3, 4])));
+	// the truncated chains are reported
+	assert_eq!(frames[0].missing, vec![(4, 9), (20, 7)]);
+	// a channel without vectors has no values
+	assert_eq!(frames[0].channels[0].get_aux_values(), None);
+}
+
+#[test]
+fn accessor_chains_with_missing_structures() {
+	let file = TempFile::new("missing_accessor_structures");
+	let path: &str = file.get_path();
+	let mut header = FrameH::new("TEST", 1, 0, 1000., 1.);
+	// the second detector is missing
+	header.set_links((0, 0), (0, 0), (5, 0), (0, 0));
+	let mut detector = FrDetector::new("LIGO_HANFORD", "H1", -2.08, 0.81, 142.554);
+	detector.set_links((5, 3));
+	write_frame_file(path, &header, vec![write_structure(&detector, 0)]);
+	let frames: Vec<Frame> = read_file(path, "", 0., 1e10);
+	assert_eq!(frames[0].get_detect_proc().len(), 1);
+	assert_eq!(frames[0].missing, vec![(5, 3)]);
+}
+
+fn message(instance: u32, alarm: &str, severity: u32, gps_sec: u32, next: (u16, u32)) -> Vec<u8> {
+	let mut body: Vec<u8> = Vec::new();
+	write_one_string(&mut body, alarm);
+	write_one_string(&mut body, "");
+	for value in [severity, gps_sec, 0] {
+		write_u32(&mut body, value);
+	}
+	write_ptr(&mut body, next);
+	write_body(10, instance, body)
+}
+
+#[test]
+fn messages_found_by_time_and_severity() {
+	let file = TempFile::new("messages");
+	let path: &str = file.get_path();
+	let mut header = FrameH::new("TEST", 1, 0, 1000., 10.);
+	header.set_links((12, 0), (0, 0), (0, 0), (0, 0));
+	let mut raw: Vec<u8> = Vec::new();
+	write_one_string(&mut raw, "");
+	for pointer in [(0, 0), (0, 0), (0, 0), (10, 0), (0, 0)] {
+		write_ptr(&mut raw, pointer);
+	}
+	write_frame_file(path, &header, vec![
+		write_body(12, 0, raw),
+		message(0, "LOCK", 1, 1001, (10, 1)),
+		message(1, "GLITCH", 3, 1004, (10, 2)),
+		message(2, "SATURATION", 5, 1008, (0, 0)),
+	]);
+	let alarms = |gps_start: f64, gps_end: f64, severity_min: u32|
+		read_messages(path, gps_start, gps_end, severity_min).iter()
+		.map(|x| x.get_alarm()).collect::<Vec<String>>();
+	assert_eq!(alarms(0., 1e10, 0), vec!["LOCK", "GLITCH", "SATURATION"]);
+	assert_eq!(alarms(1002., 1e10, 0), vec!["GLITCH", "SATURATION"]);
+	assert_eq!(alarms(1000., 1005., 2), vec!["GLITCH"]);
+	assert_eq!(alarms(0., 1e10, 6), Vec::<String>::new());
+}
@@ -0,0 +1,517 @@
+/* --------------------------------------------------------------------------------------------- *
+ * This file contains the parsing of the unit strings of the frame structures
+ * A unit is stored as a scale factor and the exponents of the base dimensions:
+ *	-	m, kg, s, A, K, mol, cd (SI base units)
+ *	-	counts (ADC counts)
+ *	-	strain
+ * Unit strings that can not be parsed are kept verbatim.
+ * --------------------------------------------------------------------------------------------- */
+
+use std::fmt;
+use std::ops::{
+	Add,
+	Mul,
+	Div,
+	Neg,
+};
+
+#[cfg(test)]
+mod tests;
+
+/* --------------------------------------------------------------------------------------------- *
+ * define rational exponent
+ * --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Exponent {
+	num: i32,
+	den: i32,
+}
+
+impl Exponent {
+
+	pub fn new(num: i32, den: i32) -> Self {
+		assert!(den != 0, "The denominator of an exponent can not be null.");
+		match Self::reduce(num as i128, den as i128) {
+			Some(x) => x,
+			None => panic!("The exponent {}/{} is too large.", num, den),
+		}
+	}
+	// sum and product of exponents, None if the result does not fit in the exponent
+	pub fn checked_add(self, other: Self) -> Option<Self> {
+		Self::reduce(self.num as i128 * other.den as i128 + other.num as i128 * self.den as i128,
+			self.den as i128 * other.den as i128)
+	}
+	pub fn checked_mul(self, other: Self) -> Option<Self> {
+		Self::reduce(self.num as i128 * other.num as i128, self.den as i128 * other.den as i128)
+	}
+	pub fn get_num(&self) -> i32 {
+		self.num
+	}
+	pub fn get_den(&self) -> i32 {
+		self.den
+	}
+	pub fn is_zero(&self) -> bool {
+		self.num == 0
+	}
+	pub fn to_f64(&self) -> f64 {
+		(self.num as f64) / (self.den as f64)
+	}
+
+	// irreducible fraction, with a positive denominator
+	fn reduce(num: i128, den: i128) -> Option<Self> {
+		let divisor: i128 = gcd(num.abs(), den.abs()).max(1) * den.signum();
+		Some(Exponent {
+			num: i32::try_from(num / divisor).ok()?,
+			den: i32::try_from(den / divisor).ok()?,
+		})
+	}
+}
+
+impl Add for Exponent {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		match self.checked_add(other) {
+			Some(x) => x,
+			None => panic!("The sum of the exponents {} and {} is too large.", self, other),
+		}
+	}
+}
+impl Neg for Exponent {
+	type Output = Self;
+	fn neg(self) -> Self {
+		match Self::reduce(-(self.num as i128), self.den as i128) {
+			Some(x) => x,
+			None => panic!("The opposite of the exponent {} is too large.", self),
+		}
+	}
+}
+impl Mul for Exponent {
+	type Output = Self;
+	fn mul(self, other: Self) -> Self {
+		match self.checked_mul(other) {
+			Some(x) => x,
+			None => panic!("The product of the exponents {} and {} is too large.", self, other),
+		}
+	}
+}
+impl fmt::Display for Exponent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.den {
+			1 => write!(f, "{}", self.num),
+			_ => write!(f, "{}/{}", self.num, self.den),
+		}
+	}
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+	if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * define unit
+ * --------------------------------------------------------------------------------------------- */
+pub const DIMENSION_NAMES: [&str; 9] = ["m", "kg", "s", "A", "K", "mol", "cd", "counts", "strain"];
+const NULL: Exponent = Exponent { num: 0, den: 1 };
+
+#[derive(Debug, Clone)]
+pub struct Unit {
+	scale: f64,
+	dimensions: [Exponent; 9],
+	// original string of the unit, empty for units built by arithmetic
+	symbol: String,
+	// false if the string could not be parsed
+	known: bool,
+}
+
+impl Unit {
+
+	// dimensionless unit
+	pub fn one() -> Self {
+		Unit {
+			scale: 1.,
+			dimensions: [NULL; 9],
+			symbol: String::new(),
+			known: true,
+		}
+	}
+
+	// parse a unit string, if it is not recognized the string is kept verbatim
+	pub fn parse(symbol: &str) -> Self {
+		let tokens: Vec<Token> = tokenize(symbol);
+		let mut position: usize = 0;
+		let parsed = parse_product(&tokens, &mut position);
+		let mut output: Unit = match parsed {
+			_ if tokens.is_empty() => Unit::one(),
+			Some(x) if position == tokens.len() => x,
+			_ => Unit {
+				scale: 1.,
+				dimensions: [NULL; 9],
+				symbol: String::new(),
+				known: false,
+			},
+		};
+		output.symbol = symbol.to_string();
+		output
+	}
+
+	pub fn get_scale(&self) -> f64 {
+		self.scale
+	}
+	pub fn get_dimensions(&self) -> [Exponent; 9] {
+		self.dimensions
+	}
+	pub fn get_symbol(&self) -> String {
+		self.symbol.clone()
+	}
+	pub fn is_known(&self) -> bool {
+		self.known
+	}
+	pub fn is_dimensionless(&self) -> bool {
+		self.known & self.dimensions.iter().all(|x| x.is_zero())
+	}
+	// two units are compatible if they have the same dimensions, whatever their scale
+	pub fn is_compatible(&self, other: &Self) -> bool {
+		match (self.known, other.known) {
+			(true, true) => self.dimensions == other.dimensions,
+			_ => self.symbol == other.symbol,
+		}
+	}
+	// factor to apply to values expressed in self to express them in other
+	pub fn conversion_factor(&self, other: &Self) -> f64 {
+		assert!(self.is_compatible(other), "The units '{}' and '{}' are not compatible.",
+			self, other);
+		match (self.known, other.known) {
+			(true, true) => self.scale / other.scale,
+			_ => 1.,
+		}
+	}
+	// the unit is unknown if one of its exponents gets too large
+	pub fn powf(&self, power: Exponent) -> Self {
+		let mut dimensions: [Exponent; 9] = self.dimensions;
+		for x in dimensions.iter_mut() {
+			match x.checked_mul(power) {
+				Some(y) => *x = y,
+				None => return Self::unknown(format!("({})^{}", self, power)),
+			}
+		}
+		match self.known {
+			true => Unit {
+				scale: self.scale.powf(power.to_f64()),
+				dimensions,
+				symbol: String::new(),
+				known: true,
+			},
+			false => Self::unknown(format!("({})^{}", self, power)),
+		}
+	}
+
+	fn unknown(symbol: String) -> Self {
+		Unit {
+			scale: 1.,
+			dimensions: [NULL; 9],
+			symbol,
+			known: false,
+		}
+	}
+
+	// representation of the unit from its dimensions
+	fn canonical(&self) -> String {
+		let mut terms: Vec<String> = Vec::new();
+		if self.scale != 1. {
+			terms.push(format!("{}", self.scale));
+		}
+		for (name, exponent) in DIMENSION_NAMES.iter().zip(self.dimensions.iter()) {
+			match (exponent.num, exponent.den) {
+				(0, _) => {},
+				(1, 1) => terms.push(name.to_string()),
+				_ => terms.push(format!("{}^{}", name, exponent)),
+			}
+		}
+		terms.join(" ")
+	}
+}
+
+impl PartialEq for Unit {
+	fn eq(&self, other: &Self) -> bool {
+		match (self.known, other.known) {
+			(true, true) => (self.scale == other.scale) & (self.dimensions == other.dimensions),
+			_ => self.symbol == other.symbol,
+		}
+	}
+}
+
+impl Mul for Unit {
+	type Output = Self;
+	fn mul(self, other: Self) -> Self {
+		match (self.known, other.known) {
+			(true, true) => {
+				let mut dimensions: [Exponent; 9] = self.dimensions;
+				for (x, y) in dimensions.iter_mut().zip(other.dimensions.iter()) {
+					match x.checked_add(*y) {
+						Some(z) => *x = z,
+						None => return Self::unknown(format!("({}) ({})", self, other)),
+					}
+				}
+				Unit {
+					scale: self.scale * other.scale,
+					dimensions,
+					symbol: String::new(),
+					known: true,
+				}
+			},
+			_ => Self::unknown(format!("({}) ({})", self, other)),
+		}
+	}
+}
+
+impl Div for Unit {
+	type Output = Self;
+	fn div(self, other: Self) -> Self {
+		match (self.known, other.known) {
+			(true, true) => self * other.powf(Exponent::new(-1, 1)),
+			_ => Self::unknown(format!("({})/({})", self, other)),
+		}
+	}
+}
+
+impl fmt::Display for Unit {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.symbol.is_empty() {
+			true => write!(f, "{}", self.canonical()),
+			false => write!(f, "{}", self.symbol),
+		}
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * unit symbols
+ * --------------------------------------------------------------------------------------------- */
+// scale and dimensions (m, kg, s, A, K, mol, cd, counts, strain) of the known symbols
+fn base_unit(symbol: &str) -> Option<(f64, [i32; 9])> {
+
+	let output: (f64, [i32; 9]) = match symbol {
+		"m" => (1., [1, 0, 0, 0, 0, 0, 0, 0, 0]),
+		"g" => (1e-3, [0, 1, 0, 0, 0, 0, 0, 0, 0]),
+		"s" | "sec" => (1., [0, 0, 1, 0, 0, 0, 0, 0, 0]),
+		"min" => (60., [0, 0, 1, 0, 0, 0, 0, 0, 0]),
+		"h" => (3600., [0, 0, 1, 0, 0, 0, 0, 0, 0]),
+		"A" => (1., [0, 0, 0, 1, 0, 0, 0, 0, 0]),
+		"K" => (1., [0, 0, 0, 0, 1, 0, 0, 0, 0]),
+		"mol" => (1., [0, 0, 0, 0, 0, 1, 0, 0, 0]),
+		"cd" => (1., [0, 0, 0, 0, 0, 0, 1, 0, 0]),
+		"counts" | "count" | "cts" | "ct" => (1., [0, 0, 0, 0, 0, 0, 0, 1, 0]),
+		"strain" => (1., [0, 0, 0, 0, 0, 0, 0, 0, 1]),
+		"Hz" => (1., [0, 0, -1, 0, 0, 0, 0, 0, 0]),
+		"N" => (1., [1, 1, -2, 0, 0, 0, 0, 0, 0]),
+		"Pa" => (1., [-1, 1, -2, 0, 0, 0, 0, 0, 0]),
+		"J" => (1., [2, 1, -2, 0, 0, 0, 0, 0, 0]),
+		"W" => (1., [2, 1, -3, 0, 0, 0, 0, 0, 0]),
+		"C" => (1., [0, 0, 1, 1, 0, 0, 0, 0, 0]),
+		"V" => (1., [2, 1, -3, -1, 0, 0, 0, 0, 0]),
+		"Ohm" | "ohm" | "Ω" => (1., [2, 1, -3, -2, 0, 0, 0, 0, 0]),
+		"F" => (1., [-2, -1, 4, 2, 0, 0, 0, 0, 0]),
+		"T" => (1., [0, 1, -2, -1, 0, 0, 0, 0, 0]),
+		"Wb" => (1., [2, 1, -2, -1, 0, 0, 0, 0, 0]),
+		"rad" | "sr" | "1" | "" => (1., [0, 0, 0, 0, 0, 0, 0, 0, 0]),
+		"deg" => (std::f64::consts::PI / 180., [0, 0, 0, 0, 0, 0, 0, 0, 0]),
+		_ => return None,
+	};
+	Some(output)
+}
+
+fn prefix(symbol: &str) -> Option<f64> {
+
+	let output: f64 = match symbol {
+		"f" => 1e-15,
+		"p" => 1e-12,
+		"n" => 1e-9,
+		"u" | "µ" | "μ" => 1e-6,
+		"m" => 1e-3,
+		"c" => 1e-2,
+		"d" => 1e-1,
+		"k" => 1e3,
+		"M" => 1e6,
+		"G" => 1e9,
+		"T" => 1e12,
+		_ => return None,
+	};
+	Some(output)
+}
+
+// find a symbol, either directly or as a prefix followed by a symbol
+fn symbol_unit(symbol: &str) -> Option<Unit> {
+
+	// square root of a unit, as in rtHz
+	if let Some(x) = symbol.strip_prefix("rt") {
+		return symbol_unit(x).map(|u| u.powf(Exponent::new(1, 2)));
+	}
+	let (scale, dimensions): (f64, [i32; 9]) = match base_unit(symbol) {
+		Some(x) => x,
+		None => {
+			let first: usize = symbol.chars().next()?.len_utf8();
+			let (p, rest) = symbol.split_at(first);
+			if rest.is_empty() { return None; }
+			let (scale, dimensions) = base_unit(rest)?;
+			(prefix(p)? * scale, dimensions)
+		},
+	};
+	Some(Unit {
+		scale,
+		dimensions: dimensions.map(|x| Exponent::new(x, 1)),
+		symbol: String::new(),
+		known: true,
+	})
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * unit parser
+ * --------------------------------------------------------------------------------------------- */
+#[derive(PartialEq, Debug, Clone)]
+enum Token {
+	Symbol(String),
+	Number(i32),
+	Times,
+	Over,
+	Power,
+	Minus,
+	Open,
+	Close,
+}
+
+fn tokenize(symbol: &str) -> Vec<Token> {
+
+	let mut tokens: Vec<Token> = Vec::new();
+	let mut chars = symbol.trim().chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'*' | '.' | '·' => tokens.push(Token::Times),
+			' ' => {
+				// a space is a product, except around an operator
+				let last_is_operand = matches!(tokens.last(),
+					Some(Token::Symbol(_)) | Some(Token::Number(_)) | Some(Token::Close));
+				let next_is_operand = chars.peek().is_some_and(|x|
+					x.is_alphanumeric() | (*x == '(') | (*x == 'µ'));
+				if last_is_operand & next_is_operand { tokens.push(Token::Times); }
+			},
+			'/' => tokens.push(Token::Over),
+			'^' => tokens.push(Token::Power),
+			'-' => tokens.push(Token::Minus),
+			'+' => {},
+			'(' => tokens.push(Token::Open),
+			')' => tokens.push(Token::Close),
+			x if x.is_ascii_digit() => {
+				let mut number: String = x.to_string();
+				while let Some(y) = chars.peek().filter(|y| y.is_ascii_digit()) {
+					number.push(*y);
+					chars.next();
+				}
+				// a number too large is not a valid exponent, and makes the unit unknown
+				match number.parse() {
+					Ok(y) => tokens.push(Token::Number(y)),
+					Err(_) => tokens.push(Token::Symbol(number)),
+				}
+			},
+			x => {
+				let mut name: String = x.to_string();
+				while let Some(y) = chars.peek().filter(|y| y.is_alphabetic() | (**y == '_')) {
+					name.push(*y);
+					chars.next();
+				}
+				tokens.push(Token::Symbol(name));
+			},
+		}
+	}
+	tokens
+}
+
+// product := power (('*' | '/') power)*
+fn parse_product(tokens: &[Token], position: &mut usize) -> Option<Unit> {
+
+	let mut output: Unit = parse_power(tokens, position)?;
+	while let Some(token) = tokens.get(*position) {
+		match token {
+			Token::Times => {
+				*position += 1;
+				output = output * parse_power(tokens, position)?;
+			},
+			Token::Over => {
+				*position += 1;
+				output = output / parse_power(tokens, position)?;
+			},
+			_ => break,
+		}
+	}
+	Some(output)
+}
+
+// power := factor ('^' exponent)?
+fn parse_power(tokens: &[Token], position: &mut usize) -> Option<Unit> {
+
+	let factor: Unit = parse_factor(tokens, position)?;
+	if tokens.get(*position) != Some(&Token::Power) {
+		return Some(factor);
+	}
+	*position += 1;
+	let exponent: Exponent = match tokens.get(*position) {
+		Some(Token::Open) => {
+			*position += 1;
+			let x = parse_exponent(tokens, position)?;
+			if tokens.get(*position) != Some(&Token::Close) { return None; }
+			*position += 1;
+			x
+		},
+		_ => parse_exponent(tokens, position)?,
+	};
+	Some(factor.powf(exponent))
+}
+
+// exponent := '-'? number ('/' number)?, as in Hz^-1 or Hz^1/2
+fn parse_exponent(tokens: &[Token], position: &mut usize) -> Option<Exponent> {
+
+	let mut sign: i32 = 1;
+	if tokens.get(*position) == Some(&Token::Minus) {
+		sign = -1;
+		*position += 1;
+	}
+	let num: i32 = match tokens.get(*position) {
+		Some(Token::Number(x)) => *x,
+		_ => return None,
+	};
+	*position += 1;
+	let mut den: i32 = 1;
+	if let (Some(Token::Over), Some(Token::Number(x))) =
+		(tokens.get(*position), tokens.get(*position + 1)) {
+		den = *x;
+		*position += 2;
+	}
+	if den == 0 { return None; }
+	Some(Exponent::new(sign * num, den))
+}
+
+// factor := symbol | number | '(' product ')' | 'sqrt' '(' product ')'
+fn parse_factor(tokens: &[Token], position: &mut usize) -> Option<Unit> {
+
+	match tokens.get(*position)? {
+		Token::Symbol(x) if (x == "sqrt") & (tokens.get(*position + 1) == Some(&Token::Open)) => {
+			*position += 1;
+			Some(parse_factor(tokens, position)?.powf(Exponent::new(1, 2)))
+		},
+		Token::Symbol(x) => {
+			*position += 1;
+			symbol_unit(x)
+		},
+		// only a unit number is allowed, as in 1/Hz
+		Token::Number(1) => {
+			*position += 1;
+			Some(Unit::one())
+		},
+		Token::Open => {
+			*position += 1;
+			let output = parse_product(tokens, position)?;
+			if tokens.get(*position) != Some(&Token::Close) { return None; }
+			*position += 1;
+			Some(output)
+		},
+		_ => None,
+	}
+}
@@ -0,0 +1,33 @@
+/* --------------------------------------------------------------------------------------------- *
+ * Tests of the unit parser
+ * --------------------------------------------------------------------------------------------- */
+
+use crate::units::*;
+
+#[test]
+fn parse_products_and_powers() {
+	assert_eq!(Unit::parse("m^2 m"), Unit::parse("m^3"));
+	assert_eq!(Unit::parse("m/s^2"), Unit::parse("m s^-2"));
+	assert_eq!(Unit::parse("1/rtHz"), Unit::parse("Hz^-1/2"));
+	assert!(Unit::parse("m/s").is_compatible(&Unit::parse("km/h")));
+	assert!(!Unit::parse("furlong").is_known());
+}
+
+#[test]
+fn exponent_overflow_gives_unknown_unit() {
+	// exponents larger than the exponent type
+	for symbol in ["m^99999999999 m^2", "m^2147483647 m", "m^-2147483647 m^-2",
+		"(m^65536)^65536", "s^1/2147483647 s^1/2147483646", "Hz^-2147483647 s"] {
+		let unit: Unit = Unit::parse(symbol);
+		assert!(!unit.is_known(), "{}", symbol);
+		assert_eq!(unit.get_symbol(), symbol);
+		assert_eq!(unit.to_string(), symbol);
+	}
+	// the arithmetic on known units does not panic either
+	let unit: Unit = Unit::parse("m^2147483647");
+	assert!(unit.is_known());
+	assert!(!(unit.clone() * Unit::parse("m")).is_known());
+	assert!(!unit.powf(Exponent::new(2, 1)).is_known());
+	assert_eq!(Exponent::new(1, 2147483647).checked_add(Exponent::new(1, 2147483646)), None);
+	assert_eq!(Exponent::new(1, 2).checked_add(Exponent::new(1, 3)), Some(Exponent::new(5, 6)));
+}
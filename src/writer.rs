@@ -0,0 +1,695 @@
+/* --------------------------------------------------------------------------------------------- *
+ * This file contains the frame file writer
+ * A file is made of:
+ *	-	the file header
+ *	-	the frames: FrameH, FrRawData, FrAdcData list, FrProcData list, FrDetector list,
+ *		FrEvent list, structures not modeled by the crate, FrVect, FrEndOfFrame
+ *	-	FrTOC, holding the position of the frames and of their structures
+ *	-	FrEndOfFile
+ * Each structure type is described by its FrSH and FrSE dictionary entries before its first
+ * instance. The instances are numbered by class inside each frame. Each structure carries its
+ * CRC check sum, and FrEndOfFile the check sums of the file header and of the whole file.
+ * --------------------------------------------------------------------------------------------- */
+
+use std::fs::File;
+use std::collections::BTreeMap;
+use std::io::{
+	BufWriter,
+	Write,
+};
+
+use crate::{
+	Channel,
+	Unknown,
+};
+use crate::structures::{
+	*,
+	bytes::*,
+	checksum::Crc,
+	compression::Compression,
+	vector::VectData,
+};
+
+#[cfg(test)]
+mod tests;
+
+/* --------------------------------------------------------------------------------------------- *
+ * define file writer
+ * --------------------------------------------------------------------------------------------- */
+pub struct FileWriter {
+	file: BufWriter<File>,
+	// number of bytes already written, and their check sum
+	position: u64,
+	crc: Crc,
+	n_frames: u32,
+	// classes already described in the dictionary, with their names
+	dictionary: Vec<(u16, String)>,
+	n_sh: u32,
+	n_se: u32,
+	// table of contents filled as the frames are written
+	toc: FrTOC,
+	// detectors and events attached to the next frame
+	detectors: Vec<FrDetector>,
+	events: Vec<FrEvent>,
+	unknown: Vec<Unknown>,
+	// compression of the vectors: by channel name, by rule on the class of the channel
+	// structure and the data type of the vector, then by default
+	channel_compression: BTreeMap<String, Compression>,
+	compression_rules: Vec<(u16, u16, Compression)>,
+	compression: Compression,
+}
+
+impl FileWriter {
+
+	// create the file and write its header
+	pub fn create(path: &str) -> Self {
+		let file = match File::create(path) {
+			Ok(x) => x,
+			Err(e) => panic!("Can not create the file {}: {}", path, e),
+		};
+		let mut writer = FileWriter {
+			file: BufWriter::new(file),
+			position: 0,
+			crc: Crc::new(),
+			n_frames: 0,
+			dictionary: Vec::new(),
+			n_sh: 0,
+			n_se: 0,
+			toc: FrTOC::new(),
+			detectors: Vec::new(),
+			events: Vec::new(),
+			unknown: Vec::new(),
+			channel_compression: BTreeMap::new(),
+			compression_rules: Vec::new(),
+			compression: Compression::Raw,
+		};
+		let mut buffer: Vec<u8> = Vec::new();
+		write_file_header(&mut buffer);
+		writer.write_bytes(&buffer);
+		writer
+	}
+
+	// compression of the vectors not selected by channel name or by rule, raw by default
+	pub fn set_compression(&mut self, compression: Compression) {
+		self.compression = compression;
+	}
+
+	// compression of the vectors of a channel
+	pub fn set_channel_compression(&mut self, name: &str, compression: Compression) {
+		self.channel_compression.insert(name.to_string(), compression);
+	}
+
+	// compression of the vectors of a data type held by the channels of a class (4 for
+	// FrAdcData, 11 for FrProcData), e.g. (4, 1, Compression::ZeroSuppress) for the 16 bits ADC
+	// channels; the first matching rule is used
+	pub fn add_compression_rule(&mut self, class: u16, data_type: u16, compression: Compression) {
+		self.compression_rules.push((class, data_type, compression));
+	}
+
+	// add a detector to the next frame written, as a FrDetector of its detectProc list
+	pub fn add_detector(&mut self, detector: FrDetector) {
+		self.detectors.push(detector);
+	}
+
+	// add an event to the next frame written
+	pub fn add_event(&mut self, event: FrEvent) {
+		self.events.push(event);
+	}
+
+	// add a structure not modeled by the crate to the next frame written, with its dictionary
+	// entries if its class is not described yet; its pointers are renumbered: the vector chains
+	// are written with the frame, and the pointers to the header, the FrRawData, the channels,
+	// the detectors, the events and the structures not modeled written in the same frame follow
+	// their new instances; the frame is not written if a pointed structure is not part of it, or
+	// if the dictionary of the structure does not locate its pointers
+	pub fn add_unknown(&mut self, unknown: Unknown) {
+		self.unknown.push(unknown);
+	}
+
+	// write a frame with its channels, the channels must hold a FrAdcData or a FrProcData
+	// structure; the pointers between the structures are set by the writer, which does not write
+	// the history, user data, simulated detectors, data and events, summaries, auxiliary data
+	// and tables, nor the tables and history of the channels, the auxiliary vectors and tables
+	// of the detectors and the data and tables of the events: the frame is not written if the
+	// header, a channel, a detector or an event points to such structures, their clear_links
+	// function dropping these links
+	pub fn write_frame(&mut self, mut header: FrameH, channels: Vec<Channel>) {
+
+		// link the structures: FrameH -> FrRawData -> FrAdcData list, and FrameH -> FrProcData
+		// list, each channel pointing to its vector chains
+		let n_adc: usize = channels.iter()
+			.filter(|x| matches!(x.data, Structure::FrAdcData(_))).count();
+		let n_proc: usize = channels.iter()
+			.filter(|x| matches!(x.data, Structure::FrProcData(_))).count();
+		let mut adc_list: Vec<FrAdcData> = Vec::new();
+		let mut proc_list: Vec<FrProcData> = Vec::new();
+		let mut vect_list: Vec<FrVect> = Vec::new();
+		// new pointer of each structure written, from its class and instance given as input
+		let mut targets: Vec<((u16, u32), (u16, u32))> = vec![((3, header.get_instance()), (3, 0))];
+		for mut channel in channels {
+			let name: String = channel.data.get_name();
+			let class: u16 = channel.data.get_class();
+			let instance: u32 = channel.data.get_instance();
+			for vect in channel.vect.iter_mut().chain(channel.aux.iter_mut()) {
+				vect.compress(&self.get_compression(&name, class, vect.get_data_type()));
+			}
+			let data: (u16, u32) = chain_vect(channel.vect, &mut vect_list);
+			let aux: (u16, u32) = chain_vect(channel.aux, &mut vect_list);
+			match channel.data {
+				Structure::FrAdcData(mut x) => {
+					x.set_links(data, aux, next_pointer(4, adc_list.len(), n_adc));
+					targets.push(((4, instance), (4, adc_list.len() as u32)));
+					adc_list.push(x);
+				},
+				Structure::FrProcData(mut x) => {
+					x.set_links(data, aux, next_pointer(11, proc_list.len(), n_proc));
+					targets.push(((11, instance), (11, proc_list.len() as u32)));
+					proc_list.push(x);
+				},
+				x => panic!("The structure {} can not be written yet.", x.get_name()),
+			}
+		}
+		let raw_data: Option<FrRawData> = match n_adc {
+			0 => None,
+			_ => Some(FrRawData::new("", (4, 0))),
+		};
+		let mut detectors: Vec<FrDetector> = std::mem::take(&mut self.detectors);
+		let n_detectors: usize = detectors.len();
+		for (i, detector) in detectors.iter_mut().enumerate() {
+			targets.push(((5, detector.get_instance()), (5, i as u32)));
+			detector.set_links(next_pointer(5, i, n_detectors));
+		}
+		let mut events: Vec<FrEvent> = std::mem::take(&mut self.events);
+		let n_events: usize = events.len();
+		for (i, event) in events.iter_mut().enumerate() {
+			targets.push(((8, event.get_instance()), (8, i as u32)));
+			event.set_links(next_pointer(8, i, n_events));
+		}
+		// structures not modeled, numbered by class in the frame
+		let mut instances: Vec<u32> = Vec::new();
+		let mut counts: BTreeMap<u16, u32> = BTreeMap::new();
+		for unknown in self.unknown.iter() {
+			let count: &mut u32 = counts.entry(unknown.get_class()).or_insert(0);
+			targets.push(((unknown.get_class(), unknown.data.get_instance()),
+				(unknown.get_class(), *count)));
+			instances.push(*count);
+			*count += 1;
+		}
+		let mut unknown_list: Vec<(FrUnknown, u32)> = Vec::new();
+		for (unknown, instance) in std::mem::take(&mut self.unknown).into_iter().zip(instances) {
+			let mut data: FrUnknown = unknown.data;
+			let mut chains = unknown.vect.into_iter();
+			// the pointers of a structure whose dictionary does not describe its bytes can not
+			// be renumbered
+			let pointers: Vec<(u16, u32)> = match data.get_pointers() {
+				Some(x) => x,
+				None => panic!("The pointers of the {} structure can not be located from its \
+					dictionary, the structure can not be written.", data.get_name()),
+			};
+			let pointers: Vec<(u16, u32)> = pointers.into_iter().map(|x| match x {
+				(0, _) => (0, 0),
+				(20, _) => chain_vect(chains.next().unwrap_or_default(), &mut vect_list),
+				// a frame holds one FrRawData
+				(12, _) if raw_data.is_some() => (12, 0),
+				x => match targets.iter().find(|y| y.0 == x) {
+					Some(y) => y.1,
+					None => panic!("The structure ({}, {}) pointed to by a {} structure is not \
+						written with the frame.", x.0, x.1, data.get_name()),
+				},
+			}).collect();
+			data.set_pointers(&pointers);
+			unknown_list.push((data, instance));
+		}
+		header.set_links(
+			if raw_data.is_some() { (12, 0) } else { (0, 0) },
+			if n_proc > 0 { (11, 0) } else { (0, 0) },
+			if n_detectors > 0 { (5, 0) } else { (0, 0) },
+			if n_events > 0 { (8, 0) } else { (0, 0) },
+		);
+
+		// write the structures and index them in the table of contents
+		let position: u64 = self.write_one(&header, 0);
+		if let Some(ref raw) = raw_data {
+			self.write_one(raw, 0);
+		}
+		let mut adc_positions: Vec<u64> = Vec::new();
+		for (i, adc) in adc_list.iter().enumerate() {
+			adc_positions.push(self.write_one(adc, i as u32));
+		}
+		self.toc.add_frame(&header, position, adc_positions.first().copied().unwrap_or(0));
+		for (adc, position) in adc_list.iter().zip(adc_positions) {
+			self.toc.add_adc(adc, position);
+		}
+		for (i, proc) in proc_list.iter().enumerate() {
+			let position: u64 = self.write_one(proc, i as u32);
+			self.toc.add_proc(proc, position);
+		}
+		for (i, detector) in detectors.iter().enumerate() {
+			let position: u64 = self.write_one(detector, i as u32);
+			self.toc.add_detector(detector, position);
+		}
+		for (i, event) in events.iter().enumerate() {
+			let position: u64 = self.write_one(event, i as u32);
+			self.toc.add_event(event, position);
+		}
+		for (unknown, instance) in unknown_list.iter() {
+			self.write_unknown(unknown, *instance);
+		}
+		for (i, vect) in vect_list.iter().enumerate() {
+			self.write_one(vect, i as u32);
+		}
+		self.write_one(&FrEndOfFrame::new(&header), 0);
+		self.n_frames += 1;
+	}
+
+	// write the table of contents and the end of file structure, then flush the file
+	pub fn close(mut self) {
+		let mut toc: FrTOC = std::mem::take(&mut self.toc);
+		// the dictionary listed in the table of contents includes FrTOC and FrEndOfFile
+		let mut classes: Vec<(u16, String)> = self.dictionary.clone();
+		for (class, name) in [(19, FrTOC::dictionary().0), (6, FrEndOfFile::dictionary().0)] {
+			if !classes.iter().any(|x| x.0 == class) {
+				classes.push((class, name.to_string()));
+			}
+		}
+		toc.set_dictionary(classes);
+		let toc_position: u64 = self.write_one(&toc, 0);
+
+		// the FrEndOfFile structure is 46 bytes long
+		let mut n_bytes: u64 = self.position + 46;
+		if !self.dictionary.iter().any(|x| x.0 == 6) {
+			n_bytes += self.dictionary_size::<FrEndOfFile>();
+		}
+		let end_of_file = FrEndOfFile::new(self.n_frames, n_bytes, n_bytes - toc_position);
+		self.write_one(&end_of_file, 0);
+		// the file ends with the check sum of all the previous bytes
+		let mut buffer: Vec<u8> = Vec::new();
+		write_u32(&mut buffer, self.crc.finish());
+		self.write_bytes(&buffer);
+		if let Err(e) = self.file.flush() {
+			panic!("Can not write the file: {}", e);
+		}
+	}
+
+	/* ----------------------------------------------------------------------------------------- */
+	fn get_compression(&self, name: &str, class: u16, data_type: u16) -> Compression {
+		if let Some(x) = self.channel_compression.get(name) {
+			return *x;
+		}
+		match self.compression_rules.iter().find(|x| (x.0 == class) & (x.1 == data_type)) {
+			Some(x) => x.2,
+			None => self.compression,
+		}
+	}
+
+	fn write_bytes(&mut self, buffer: &[u8]) {
+		if let Err(e) = self.file.write_all(buffer) {
+			panic!("Can not write the file: {}", e);
+		}
+		self.position += buffer.len() as u64;
+		self.crc.update(buffer);
+	}
+
+	// dictionary entries of a structure type: one FrSH and one FrSE per element
+	fn dictionary_entries(&mut self, class: u16, name: &str, elements: &[(&str, &str)])
+		-> Vec<u8> {
+		let mut buffer: Vec<u8> = write_structure(&FrSH::new(name, class, ""), self.n_sh);
+		self.n_sh += 1;
+		for (element, element_type) in elements.iter() {
+			buffer.append(&mut write_structure(&FrSE::new(element, element_type, ""), self.n_se));
+			self.n_se += 1;
+		}
+		buffer
+	}
+
+	fn dictionary_size<T: Reader + Writer>(&self) -> u64 {
+		let (name, elements) = T::dictionary();
+		let mut size: u64 = write_structure(&FrSH::new(name, 0, ""), 0).len() as u64;
+		for (element, element_type) in elements {
+			size += write_structure(&FrSE::new(element, element_type, ""), 0).len() as u64;
+		}
+		size
+	}
+
+	// write a structure, preceded by its dictionary the first time its class is written, and
+	// return the position of the structure in the file
+	fn write_one<T: Reader + Writer>(&mut self, structure: &T, instance: u32) -> u64 {
+		let class: u16 = structure.class();
+		if !self.dictionary.iter().any(|x| x.0 == class) {
+			let (name, elements) = T::dictionary();
+			let buffer: Vec<u8> = self.dictionary_entries(class, name, &elements);
+			self.write_bytes(&buffer);
+			self.dictionary.push((class, name.to_string()));
+		}
+		let position: u64 = self.position;
+		let buffer: Vec<u8> = write_structure(structure, instance);
+		self.write_bytes(&buffer);
+		position
+	}
+
+	// write a structure not modeled, preceded by its dictionary entries the first time its
+	// class is written
+	fn write_unknown(&mut self, unknown: &FrUnknown, instance: u32) {
+		let class: u16 = unknown.get_class();
+		if !self.dictionary.iter().any(|x| x.0 == class) {
+			let elements: Vec<(String, String)> = unknown.get_elements();
+			let elements: Vec<(&str, &str)> = elements.iter()
+				.map(|(x, y)| (x.as_str(), y.as_str())).collect();
+			let buffer: Vec<u8> = self.dictionary_entries(class, &unknown.get_name(), &elements);
+			self.write_bytes(&buffer);
+			self.dictionary.push((class, unknown.get_name()));
+		}
+		let buffer: Vec<u8> = write_body(class, instance, unknown.get_data());
+		self.write_bytes(&buffer);
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * define stream writer
+ * --------------------------------------------------------------------------------------------- */
+// samples of a channel waiting to be written, start being the index of the first sample counted
+// from GPS time 0
+struct StreamChannel {
+	adc: FrAdcData,
+	start: i64,
+	data: Option<VectData>,
+	// false for the samples missing from the packets, which are filled with zeros
+	valid: Vec<bool>,
+}
+
+impl StreamChannel {
+	// index of the sample at the given gps time
+	fn index(&self, gps: f64) -> i64 {
+		(gps * self.adc.get_sample_rate()).round() as i64
+	}
+
+	fn end(&self) -> i64 {
+		self.start + self.valid.len() as i64
+	}
+
+	// take the samples from index a to index b, missing samples being filled with zeros; return
+	// None if there are no data, and the samples with a flag set if some were missing
+	fn take(&mut self, a: i64, b: i64) -> Option<(VectData, bool)> {
+		let data: &mut VectData = self.data.as_mut()?;
+		// pad the beginning up to the index a
+		if self.start > a {
+			let mut padding: VectData = data.split_off(data.len());
+			padding.resize((self.start - a) as usize);
+			let values: VectData = std::mem::replace(data, padding);
+			data.append(values);
+			let mut valid: Vec<bool> = vec![false; (self.start - a) as usize];
+			valid.append(&mut self.valid);
+			self.valid = valid;
+			self.start = a;
+		}
+		let n: usize = (b - a).max(0) as usize;
+		let rest: VectData = data.split_off(n.min(self.valid.len()));
+		let mut chunk: VectData = std::mem::replace(data, rest);
+		let rest_valid: Vec<bool> = self.valid.split_off(n.min(self.valid.len()));
+		let mut valid: Vec<bool> = std::mem::replace(&mut self.valid, rest_valid);
+		self.start = b.max(self.start);
+		chunk.resize(n);
+		valid.resize(n, false);
+		Some((chunk, valid.iter().any(|x| !x)))
+	}
+}
+
+// writer of frames from blocks of samples: the samples are gathered into frames of frame_length
+// seconds, starting on multiples of frame_length, and the frames into files of frames_per_file
+// frames, named directory/prefix-GPS-DURATION.gwf after the span of the frames they hold (a file
+// being written has the GPS start and DURATION of a whole file); a channel which does not cover
+// a whole frame is written with zeros in place of the missing samples and a non zero data valid
+// flag (or left out if none of its samples were received yet), and is reported by
+// get_short_channels; the samples received after their frame was written are counted by
+// get_dropped_samples
+pub struct FrameStreamWriter {
+	directory: String,
+	prefix: String,
+	name: String,
+	run: u32,
+	frame_length: u32,
+	frames_per_file: u32,
+	compression: Compression,
+	channels: BTreeMap<String, StreamChannel>,
+	// start of the next frame, set by the first block of samples and moved back by a block
+	// starting earlier in a frame not written yet
+	frame_start: Option<u64>,
+	// end of the last frame written
+	written_end: u64,
+	n_frames: u32,
+	// file being written, with its nominal start and the start of its first frame
+	file: Option<(u64, u64, FileWriter)>,
+	short_channels: Vec<(u64, String)>,
+	// number of samples of each channel received too late for their frame
+	dropped: BTreeMap<String, u64>,
+}
+
+impl FrameStreamWriter {
+
+	pub fn new(directory: &str, prefix: &str, frame_length: u32, frames_per_file: u32) -> Self {
+		if (frame_length == 0) | (frames_per_file == 0) {
+			panic!("The frame length and the number of frames per file must be positive.");
+		}
+		FrameStreamWriter {
+			directory: directory.to_string(),
+			prefix: prefix.to_string(),
+			name: prefix.to_string(),
+			run: 0,
+			frame_length,
+			frames_per_file,
+			compression: Compression::Raw,
+			channels: BTreeMap::new(),
+			frame_start: None,
+			written_end: 0,
+			n_frames: 0,
+			file: None,
+			short_channels: Vec::new(),
+			dropped: BTreeMap::new(),
+		}
+	}
+
+	// name and run number written in the frame headers, the name being the prefix by default
+	pub fn set_frame_name(&mut self, name: &str, run: u32) {
+		self.name = name.to_string();
+		self.run = run;
+	}
+
+	// compression of the vectors of the files
+	pub fn set_compression(&mut self, compression: Compression) {
+		self.compression = compression;
+	}
+
+	// add a channel, described by its FrAdcData structure
+	pub fn add_channel(&mut self, adc: FrAdcData) {
+		let name: String = adc.get_name();
+		self.channels.insert(name, StreamChannel {
+			adc,
+			start: 0,
+			data: None,
+			valid: Vec::new(),
+		});
+	}
+
+	// add a block of samples of a channel, the first sample being at the gps time; the samples
+	// overlapping those already received, or belonging to frames already written, are dropped,
+	// the latter being counted by get_dropped_samples
+	pub fn push(&mut self, name: &str, gps: f64, mut values: VectData) {
+
+		// the next frame moves back to the frame of the block, if it is not written yet
+		let frame_length: u64 = self.frame_length as u64;
+		let block_start: u64 = ((gps.max(0.) as u64) / frame_length * frame_length)
+			.max(self.written_end);
+		let frame_start: u64 = match self.frame_start {
+			Some(x) => x.min(block_start),
+			None => block_start,
+		};
+		self.frame_start = Some(frame_start);
+		let channel: &mut StreamChannel = match self.channels.get_mut(name) {
+			Some(x) => x,
+			None => panic!("The channel {} has not been added to the stream.", name),
+		};
+		let mut index: i64 = channel.index(gps);
+		let first: i64 = channel.index(frame_start as f64).max(channel.end());
+		// drop the samples already received, and those of the frames already written
+		if index < first {
+			let n: usize = ((first - index) as usize).min(values.len());
+			let late: usize = ((channel.index(self.written_end as f64) - index).max(0) as usize)
+				.min(n);
+			if late > 0 {
+				*self.dropped.entry(name.to_string()).or_insert(0) += late as u64;
+			}
+			values = values.split_off(n);
+			index = first;
+		}
+		if values.is_empty() {
+			return;
+		}
+		match channel.data {
+			Some(ref mut data) if !data.is_empty() => {
+				// fill the gap between the blocks with zeros
+				let gap: usize = (index - channel.start) as usize;
+				data.resize(gap);
+				channel.valid.resize(gap, false);
+				channel.valid.resize(gap + values.len(), true);
+				data.append(values);
+			},
+			// first block, or all the samples received are written: the buffer starts at the
+			// block, the samples missing from a frame being filled with zeros when it is written
+			_ => {
+				channel.start = index;
+				channel.valid = vec![true; values.len()];
+				channel.data = Some(values);
+			},
+		}
+		self.write_frames(false);
+	}
+
+	// channels which did not cover a whole frame, with the start of the frame
+	pub fn get_short_channels(&self) -> Vec<(u64, String)> {
+		self.short_channels.clone()
+	}
+
+	// channels with samples dropped because their frame was already written, with the number
+	// of samples dropped
+	pub fn get_dropped_samples(&self) -> Vec<(String, u64)> {
+		self.dropped.iter().map(|(x, y)| (x.clone(), *y)).collect()
+	}
+
+	// write the remaining samples and close the last file, return the channels which did not
+	// cover a whole frame
+	pub fn close(mut self) -> Vec<(u64, String)> {
+		self.write_frames(true);
+		self.close_file();
+		self.short_channels
+	}
+
+	/* ----------------------------------------------------------------------------------------- */
+	// write the frames covered by the channels: a frame is written when all the channels cover
+	// it, when one of them goes one frame beyond it, or at the end of the stream if some
+	// samples are left; the frames without any sample are skipped
+	fn write_frames(&mut self, flush: bool) {
+
+		let frame_length: u64 = self.frame_length as u64;
+		while let Some(frame_start) = self.frame_start {
+			let frame_end: u64 = frame_start + frame_length;
+			// frame of the first sample left
+			let next: Option<i64> = self.channels.values()
+				.filter(|x| x.data.as_ref().is_some_and(|y| !y.is_empty()))
+				.map(|x| (x.start as f64 / x.adc.get_sample_rate()).floor() as i64).min();
+			match next {
+				None => return,
+				Some(x) if x >= frame_end as i64 => {
+					self.frame_start = Some(x as u64 / frame_length * frame_length);
+					continue;
+				},
+				Some(_) => {},
+			}
+			let ends: Vec<(i64, i64)> = self.channels.values()
+				.filter(|x| x.data.is_some())
+				.map(|x| (x.end() - x.index(frame_end as f64),
+					x.end() - x.index((frame_end + frame_length) as f64)))
+				.collect();
+			let complete: bool = (ends.len() == self.channels.len())
+				& ends.iter().all(|x| x.0 >= 0);
+			let late: bool = ends.iter().any(|x| x.1 >= 0);
+			if !(complete | late | flush) {
+				return;
+			}
+			self.write_frame(frame_start);
+			self.written_end = frame_end;
+			self.frame_start = Some(frame_end);
+		}
+	}
+
+	// path of the file starting at the gps time start and lasting duration seconds
+	fn file_path(&self, start: u64, duration: u64) -> String {
+		format!("{}/{}-{}-{}.gwf", self.directory, self.prefix, start, duration)
+	}
+
+	// close the file being written: it is opened with the start and the length of the files,
+	// and renamed after the span of the frames written in it, from the start of its first frame
+	// to the end of its last one, if the stream starts or ends within the file
+	fn close_file(&mut self) {
+		let (file_start, start, writer): (u64, u64, FileWriter) = match self.file.take() {
+			Some(x) => x,
+			None => return,
+		};
+		writer.close();
+		let file_length: u64 = self.frame_length as u64 * self.frames_per_file as u64;
+		let (path, written): (String, String) = (self.file_path(file_start, file_length),
+			self.file_path(start, self.written_end - start));
+		if path != written {
+			if let Err(e) = std::fs::rename(&path, &written) {
+				panic!("Can not rename the file {} to {}: {}", path, written, e);
+			}
+		}
+	}
+
+	fn write_frame(&mut self, frame_start: u64) {
+
+		let frame_end: u64 = frame_start + self.frame_length as u64;
+		let mut channels: Vec<Channel> = Vec::new();
+		for (name, channel) in self.channels.iter_mut() {
+			let (a, b): (i64, i64) = (channel.index(frame_start as f64),
+				channel.index(frame_end as f64));
+			let (values, short): (VectData, bool) = match channel.take(a, b) {
+				Some(x) => x,
+				None => {
+					self.short_channels.push((frame_start, name.clone()));
+					continue;
+				},
+			};
+			let mut adc: FrAdcData = channel.adc.clone();
+			if short {
+				adc.set_valid_data(1);
+				self.short_channels.push((frame_start, name.clone()));
+			}
+			let vect = FrVect::new(name, values, 1. / adc.get_sample_rate(), 0., "s", "counts");
+			channels.push(Channel {
+				data: Structure::FrAdcData(adc),
+				vect: vec![vect],
+				aux: Vec::new(),
+			});
+		}
+
+		// open the file of the frame
+		let file_length: u64 = self.frame_length as u64 * self.frames_per_file as u64;
+		let file_start: u64 = frame_start / file_length * file_length;
+		if self.file.as_ref().is_none_or(|x| x.0 != file_start) {
+			self.close_file();
+			let mut writer = FileWriter::create(&self.file_path(file_start, file_length));
+			writer.set_compression(self.compression);
+			self.file = Some((file_start, frame_start, writer));
+		}
+		let header = FrameH::new(&self.name, self.run, self.n_frames, frame_start as f64,
+			self.frame_length as f64);
+		if let Some((_, _, ref mut writer)) = self.file {
+			writer.write_frame(header, channels);
+		}
+		self.n_frames += 1;
+	}
+}
+
+/* --------------------------------------------------------------------------------------------- *
+ * helper functions
+ * --------------------------------------------------------------------------------------------- */
+// pointer to the element following the i-th one of a list of n elements of the given class
+fn next_pointer(class: u16, i: usize, n: usize) -> (u16, u32) {
+	if i + 1 < n { (class, (i + 1) as u32) } else { (0, 0) }
+}
+
+// add a chain of vectors to the list of vectors of the frame, and return the pointer to its
+// first vector
+fn chain_vect(chain: Vec<FrVect>, vect_list: &mut Vec<FrVect>) -> (u16, u32) {
+
+	if chain.is_empty() {
+		return (0, 0);
+	}
+	let first: usize = vect_list.len();
+	let n: usize = first + chain.len();
+	for (i, mut vect) in chain.into_iter().enumerate() {
+		vect.set_next(next_pointer(20, first + i, n));
+		vect_list.push(vect);
+	}
+	(20, first as u32)
+}
@@ -0,0 +1,383 @@
+/* --------------------------------------------------------------------------------------------- *
+ * Tests of the frame writers: the files written are read back with the readers of the crate
+ * --------------------------------------------------------------------------------------------- */
+
+use crate::*;
+use crate::structures::{
+	compression::Compression,
+	vector::VectData,
+};
+use crate::structures::bytes::*;
+use crate::writer::*;
+use crate::test_util::TempFile;
+
+fn adc_channel(name: &str, values: VectData, sample_rate: f64) -> Channel {
+	Channel {
+		data: Structure::FrAdcData(FrAdcData::new(name, 0, 1, 16, sample_rate)),
+		vect: vec![FrVect::new(name, values, 1. / sample_rate, 0., "s", "counts")],
+		aux: Vec::new(),
+	}
+}
+
+fn proc_channel(name: &str, values: VectData, sample_rate: f64) -> Channel {
+	Channel {
+		data: Structure::FrProcData(FrProcData::new(name, 1, 0, 1.)),
+		vect: vec![FrVect::new(name, values, 1. / sample_rate, 0., "s", "strain")],
+		aux: Vec::new(),
+	}
+}
+
+// write two frames holding an ADC channel, a processed channel, a detector and an event
+fn write_test_file(path: &str) -> (VectData, VectData) {
+	let adc = VectData::I16((0..256).map(|i| (i * 37 % 101 - 50) as i16).collect());
+	let proc = VectData::F64((0..64).map(|i| (i as f64 * 0.1).sin()).collect());
+	let mut writer = FileWriter::create(path);
+	for frame in 0..2 {
+		writer.add_detector(FrDetector::new("LIGO_HANFORD", "H1", -2.08, 0.81, 142.554));
+		writer.add_event(FrEvent::new("burst", 1000.5 + frame as f64, 3.5));
+		let header = FrameH::new("TEST", 1, frame, 1000. + frame as f64, 1.);
+		writer.write_frame(header, vec![
+			adc_channel("H1:ADC", adc.clone(), 256.),
+			proc_channel("H1:STRAIN", proc.clone(), 64.),
+		]);
+	}
+	writer.close();
+	(adc, proc)
+}
+
+#[test]
+fn plain_round_trip() {
+	let file = TempFile::new("plain_round_trip");
+	let path: &str = file.get_path();
+	let (adc, proc) = write_test_file(path);
+
+	// sequential read
+	let frames: Vec<Frame> = read_file(path, "H1:ADC", 0., 1e10);
+	assert_eq!(frames.len(), 2);
+	for (i, frame) in frames.iter().enumerate() {
+		assert_eq!(frame.header.get_frame(), i as u32);
+		assert_eq!(frame.header.get_start(), 1000. + i as f64);
+		assert_eq!(frame.channels.len(), 1);
+		assert_eq!(frame.channels[0].get_name(), "H1:ADC");
+		assert_eq!(frame.channels[0].get_values(), Some(adc.clone()));
+		assert_eq!(frame.detectors.len(), 1);
+		assert_eq!(frame.detectors[0].get_prefix(), "H1");
+		assert_eq!(frame.events.len(), 1);
+		assert_eq!(frame.events[0].get_time(), 1000.5 + i as f64);
+	}
+	let frames: Vec<Frame> = read_file(path, "H1:STRAIN", 0., 1e10);
+	assert_eq!(frames[1].channels[0].get_values(), Some(proc));
+
+	// check sums
+	assert_eq!(verify_file(path), Vec::new());
+
+	// table of contents
+	let toc: FrTOC = read_toc(path);
+	assert_eq!(toc.get_n_frames(), 2);
+	assert_eq!(list_channels(path), vec![(String::from("H1:ADC"), 4),
+		(String::from("H1:STRAIN"), 11)]);
+	assert_eq!(toc.get_detector_index().len(), 1);
+	for (_, _, _, positions) in toc.get_adc_index() {
+		for position in positions {
+			assert!(matches!(read_structure_at(path, position), Structure::FrAdcData(_)));
+		}
+	}
+	assert_eq!(find_events(path, "burst", 0., 1e10, 0.).len(), 2);
+}
+
+#[test]
+fn events_found_by_name_time_and_amplitude() {
+	let file = TempFile::new("events");
+	let path: &str = file.get_path();
+	let mut writer = FileWriter::create(path);
+	for frame in 0..2 {
+		let start: f64 = 1000. + frame as f64;
+		writer.add_event(FrEvent::new("burst", start + 0.25, 2. + frame as f32));
+		writer.add_event(FrEvent::new("inspiral", start + 0.5, 8.));
+		writer.add_event(FrEvent::new("burst", start + 0.75, 6. + frame as f32));
+		writer.write_frame(FrameH::new("TEST", 1, frame, start, 1.),
+			vec![adc_channel("H1:ADC", VectData::I16(vec![0; 4]), 4.)]);
+	}
+	writer.close();
+
+	// the events are told apart by their amplitude
+	let amplitudes = |name: &str, gps_start: f64, gps_end: f64, amplitude_min: f32|
+		find_events(path, name, gps_start, gps_end, amplitude_min).iter()
+		.map(|x| x.get_amplitude()).collect::<Vec<f32>>();
+	assert_eq!(amplitudes("burst", 0., 1e10, 0.), vec![2., 6., 3., 7.]);
+	assert_eq!(amplitudes("burst", 1001., 1e10, 0.), vec![3., 7.]);
+	assert_eq!(amplitudes("burst", 1000., 1001.5, 5.), vec![6.]);
+	assert_eq!(amplitudes("inspiral", 1000., 1001., 0.), vec![8.]);
+	assert_eq!(amplitudes("ringdown", 0., 1e10, 0.), Vec::<f32>::new());
+}
+
+#[test]
+fn compression_round_trip() {
+	// random walk with a flat segment, and series reaching the limits of their types
+	let mut x: i64 = 0;
+	let walk: Vec<i64> = (0..1003).map(|i| {
+		x += (i * 7919 % 13) - 6;
+		if (i > 500) & (i < 600) { 0 } else { x }
+	}).collect();
+	let series: Vec<(&str, VectData)> = vec![
+		("I16", VectData::I16(walk.iter().map(|x| *x as i16).collect())),
+		("I16_LIMITS", VectData::I16((0..1003)
+			.map(|i| if i % 2 == 0 { i16::MIN } else { i16::MAX }).collect())),
+		("U16", VectData::U16(walk.iter().map(|x| (x + 30000) as u16).collect())),
+		("I32", VectData::I32(walk.iter().map(|x| (x * 100000) as i32).collect())),
+		("I32_LIMITS", VectData::I32((0..1003)
+			.map(|i| if i % 3 == 0 { i32::MIN } else { i32::MAX - i }).collect())),
+		("U32", VectData::U32(walk.iter().map(|x| (x + 1000) as u32).collect())),
+		("I64", VectData::I64(walk.clone())),
+		("I8", VectData::I8(walk.iter().map(|x| *x as i8).collect())),
+		("F32", VectData::F32(walk.iter().map(|x| *x as f32 * 0.5).collect())),
+		("F64", VectData::F64(walk.iter().map(|x| (*x as f64).sin()).collect())),
+		("ZEROS", VectData::I16(vec![0; 17])),
+		("EMPTY", VectData::I16(Vec::new())),
+	];
+	let modes = [Compression::Raw, Compression::Gzip(6), Compression::DiffGzip(6),
+		Compression::ZeroSuppress, Compression::Auto];
+	for (i, compression) in modes.iter().enumerate() {
+		let file = TempFile::new(&format!("compression_round_trip_{}", i));
+		let path: &str = file.get_path();
+		let mut writer = FileWriter::create(path);
+		writer.set_compression(*compression);
+		writer.write_frame(FrameH::new("TEST", 1, 0, 1000., 1.), series.iter()
+			.map(|(name, values)| adc_channel(name, values.clone(), 1003.)).collect());
+		writer.close();
+		for (name, values) in series.iter() {
+			let frames: Vec<Frame> = read_file(path, name, 0., 1e10);
+			assert_eq!(frames[0].channels[0].get_values().as_ref(), Some(values),
+				"{:?} {}", compression, name);
+		}
+		assert_eq!(verify_file(path), Vec::new());
+	}
+}
+
+#[test]
+fn compression_rules() {
+	let file = TempFile::new("compression_rules");
+	let path: &str = file.get_path();
+	let values = VectData::I16((0..512).map(|i| (i % 7) as i16).collect());
+	let mut writer = FileWriter::create(path);
+	writer.set_compression(Compression::Gzip(6));
+	writer.add_compression_rule(4, 1, Compression::ZeroSuppress);
+	writer.set_channel_compression("RAW", Compression::Raw);
+	writer.write_frame(FrameH::new("TEST", 1, 0, 1000., 1.), vec![
+		adc_channel("RAW", values.clone(), 512.),
+		adc_channel("RULE", values.clone(), 512.),
+		adc_channel("DEFAULT", VectData::I32((0..512).collect()), 512.),
+	]);
+	writer.close();
+	// the codes flag the data as little endian
+	for (name, compress) in [("RAW", 0x100), ("RULE", 0x105), ("DEFAULT", 0x101)] {
+		let frames: Vec<Frame> = read_file(path, name, 0., 1e10);
+		assert_eq!(frames[0].channels[0].vect[0].get_compress(), compress, "{}", name);
+	}
+}
+
+#[test]
+fn corrupted_byte_is_reported() {
+	let file = TempFile::new("corrupted_byte");
+	let path: &str = file.get_path();
+	write_test_file(path);
+	let mut bytes: Vec<u8> = std::fs::read(path).unwrap();
+	let toc: FrTOC = read_toc(path);
+	// flip a byte in the name of the first ADC channel
+	let position: u64 = toc.get_adc_index()[0].3[0];
+	bytes[position as usize + 16] ^= 0xff;
+	std::fs::write(path, &bytes).unwrap();
+	let errors: Vec<(u64, String)> = verify_file(path);
+	assert_eq!(errors.len(), 2);
+	assert_eq!(errors[0].0, position);
+	assert!(errors[1].1.contains("check sum of the file"));
+
+	// the file header is covered by the check sum held in FrEndOfFile
+	bytes[position as usize + 16] ^= 0xff;
+	bytes[20] ^= 0xff;
+	std::fs::write(path, &bytes).unwrap();
+	let errors: Vec<(u64, String)> = verify_file(path);
+	assert!(errors.iter().any(|x| (x.0 == 0) & x.1.contains("file header")));
+}
+
+// file of one frame holding an ADC channel, a detector and two structures of a class not modeled
+// by the crate, pointing to the header, the channel, the detector, a vector and each other
+fn write_unknown_file(path: &str, described: bool) {
+	let mut bytes: Vec<u8> = Vec::new();
+	write_file_header(&mut bytes);
+	let mut header = FrameH::new("TEST", 1, 0, 1000., 1.);
+	header.set_links((12, 0), (0, 0), (5, 2), (0, 0));
+	bytes.append(&mut write_structure(&header, 7));
+	bytes.append(&mut write_structure(&FrRawData::new("", (4, 3)), 0));
+	let mut adc = FrAdcData::new("H1:ADC", 0, 1, 16, 4.);
+	adc.set_links((20, 5), (0, 0), (0, 0));
+	bytes.append(&mut write_structure(&adc, 3));
+	bytes.append(&mut write_structure(&FrDetector::new("LIGO_HANFORD", "H1", -2.08, 0.81,
+		142.554), 2));
+	// dictionary of the class not modeled
+	bytes.append(&mut write_structure(&FrSH::new("FrCustom", 40, ""), 0));
+	// the size of the name given by an element not in the dictionary does not allow to walk
+	// through the bytes
+	let name_type: &str = if described { "STRING" } else { "CHAR[nName]" };
+	let elements = [("name", name_type), ("frame", "PTR_STRUCT(FrameH *)"),
+		("adc", "PTR_STRUCT(FrAdcData *)"), ("detector", "PTR_STRUCT(FrDetector *)"),
+		("data", "PTR_STRUCT(FrVect *)"), ("next", "PTR_STRUCT(FrCustom *)"), ("chkSum", "INT_4U")];
+	for (i, (name, element_type)) in elements.iter().enumerate() {
+		bytes.append(&mut write_structure(&FrSE::new(name, element_type, ""), i as u32));
+	}
+	let custom = |name: &str, instance: u32, pointers: [(u16, u32); 5]| {
+		let mut body: Vec<u8> = Vec::new();
+		write_one_string(&mut body, name);
+		for pointer in pointers {
+			write_ptr(&mut body, pointer);
+		}
+		write_body(40, instance, body)
+	};
+	bytes.append(&mut custom("first", 4, [(3, 7), (4, 3), (5, 2), (20, 6), (40, 9)]));
+	bytes.append(&mut custom("second", 9, [(3, 7), (0, 0), (0, 0), (0, 0), (0, 0)]));
+	bytes.append(&mut write_structure(&FrVect::new("H1:ADC", VectData::I16(vec![1, 2, 3, 4]),
+		0.25, 0., "s", "counts"), 5));
+	bytes.append(&mut write_structure(&FrVect::new("custom", VectData::F64(vec![0.5]), 1., 0.,
+		"", ""), 6));
+	bytes.append(&mut write_structure(&FrEndOfFrame::new(&header), 7));
+	let n_bytes: u64 = bytes.len() as u64 + 46;
+	bytes.append(&mut write_structure(&FrEndOfFile::new(1, n_bytes, 0), 0));
+	bytes.extend_from_slice(&[0; 4]);
+	std::fs::write(path, bytes).unwrap();
+}
+
+// copy the frame of the input file, with its detectors if with_detectors is set
+fn copy_unknown_frame(input: &str, output: &str, with_detectors: bool) {
+	let frame: Frame = read_file(input, "H1:ADC", 0., 1e10).remove(0);
+	let mut writer = FileWriter::create(output);
+	if with_detectors {
+		for detector in frame.detectors {
+			writer.add_detector(detector);
+		}
+	}
+	for unknown in frame.unknown {
+		writer.add_unknown(unknown);
+	}
+	writer.write_frame(frame.header, frame.channels);
+	writer.close();
+}
+
+#[test]
+fn unknown_pointers_follow_the_structures() {
+	let input_file = TempFile::new("unknown_pointers_input");
+	let input: &str = input_file.get_path();
+	let output_file = TempFile::new("unknown_pointers_output");
+	let output: &str = output_file.get_path();
+	write_unknown_file(input, true);
+	copy_unknown_frame(input, output, true);
+
+	let frame: Frame = read_file(output, "H1:ADC", 0., 1e10).remove(0);
+	assert_eq!(frame.channels[0].get_values(), Some(VectData::I16(vec![1, 2, 3, 4])));
+	assert_eq!(frame.unknown.len(), 2);
+	let pointers: Vec<(u16, u32)> = frame.unknown[0].data.get_pointers().unwrap();
+	assert_eq!(pointers[..3], [(3, 0), (4, 0), (5, 0)]);
+	assert_eq!(pointers[3].0, 20);
+	assert_eq!(pointers[4], (40, 1));
+	assert_eq!(frame.unknown[0].vect[0][0].get_values(), VectData::F64(vec![0.5]));
+	assert_eq!(frame.unknown[1].data.get_pointers(),
+		Some(vec![(3, 0), (0, 0), (0, 0), (0, 0), (0, 0)]));
+	assert_eq!(verify_file(output), Vec::new());
+}
+
+#[test]
+#[should_panic(expected = "is not written with the frame")]
+fn unknown_pointer_to_a_missing_structure() {
+	let input_file = TempFile::new("unknown_missing_input");
+	let input: &str = input_file.get_path();
+	let output_file = TempFile::new("unknown_missing_output");
+	let output: &str = output_file.get_path();
+	write_unknown_file(input, true);
+	// the detector pointed to by the first structure is left out
+	copy_unknown_frame(input, output, false);
+}
+
+#[test]
+#[should_panic(expected = "can not be located from its dictionary")]
+fn unknown_structure_not_described() {
+	let input_file = TempFile::new("unknown_not_described_input");
+	let input: &str = input_file.get_path();
+	let output_file = TempFile::new("unknown_not_described_output");
+	let output: &str = output_file.get_path();
+	write_unknown_file(input, false);
+	// the structures are read without their pointers, but can not be written back
+	let frame: Frame = read_file(input, "H1:ADC", 0., 1e10).remove(0);
+	assert_eq!(frame.unknown[0].data.get_pointers(), None);
+	assert!(frame.unknown[0].vect.is_empty());
+	copy_unknown_frame(input, output, true);
+}
+
+#[test]
+fn stream_with_gaps_and_late_packets() {
+	let directory: String = std::env::temp_dir().to_string_lossy().to_string();
+	let prefix: String = format!("rustframe_stream_{}", std::process::id());
+	let file = TempFile::at(&format!("{}/{}-1000-4.gwf", directory, prefix));
+	let mut stream = FrameStreamWriter::new(&directory, &prefix, 1, 4);
+	for name in ["A", "B"] {
+		stream.add_channel(FrAdcData::new(name, 0, 0, 16, 4.));
+	}
+	let samples = |x: i16, n: usize| VectData::I16(vec![x; n]);
+	stream.push("A", 1000., samples(1, 4));
+	stream.push("B", 1000., samples(2, 4));
+	// the first half of the second frame of A is missing
+	stream.push("A", 1001.5, samples(3, 2));
+	stream.push("B", 1001., samples(4, 4));
+	// the missing samples come once their frame is written, B is repeated
+	stream.push("A", 1001., samples(5, 2));
+	stream.push("B", 1000.5, samples(6, 4));
+	// a gap of one frame
+	stream.push("B", 1003., samples(7, 4));
+	stream.push("A", 1003., samples(8, 4));
+	assert_eq!(stream.get_dropped_samples(), vec![(String::from("A"), 2),
+		(String::from("B"), 4)]);
+	assert_eq!(stream.close(), vec![(1001, String::from("A"))]);
+
+	let path: &str = file.get_path();
+	let frames: Vec<Frame> = read_file(path, "A", 0., 1e10);
+	let starts: Vec<f64> = frames.iter().map(|x| x.header.get_start()).collect();
+	assert_eq!(starts, vec![1000., 1001., 1003.]);
+	let values: Vec<VectData> = frames.iter().map(|x| x.channels[0].get_values().unwrap())
+		.collect();
+	assert_eq!(values, vec![samples(1, 4), VectData::I16(vec![0, 0, 3, 3]), samples(8, 4)]);
+	match frames[1].channels[0].data {
+		Structure::FrAdcData(ref x) => assert_eq!(x.get_valid_data(), 1),
+		_ => panic!("The channel A is not an ADC channel."),
+	}
+	let frames: Vec<Frame> = read_file(path, "B", 0., 1e10);
+	let values: Vec<VectData> = frames.iter().map(|x| x.channels[0].get_values().unwrap())
+		.collect();
+	assert_eq!(values, vec![samples(2, 4), samples(4, 4), samples(7, 4)]);
+}
+
+#[test]
+fn stream_starting_and_ending_within_a_file() {
+	let directory: String = std::env::temp_dir().to_string_lossy().to_string();
+	let prefix: String = format!("rustframe_stream_span_{}", std::process::id());
+	// the file is named after the frames 1001 to 1003, instead of the whole file
+	let file = TempFile::at(&format!("{}/{}-1001-3.gwf", directory, prefix));
+	let whole_file = TempFile::at(&format!("{}/{}-1000-4.gwf", directory, prefix));
+	let mut stream = FrameStreamWriter::new(&directory, &prefix, 1, 4);
+	for name in ["A", "B"] {
+		stream.add_channel(FrAdcData::new(name, 0, 0, 16, 4.));
+	}
+	let samples = |x: i16, n: usize| VectData::I16(vec![x; n]);
+	// B starts one frame before A, no frame being written yet
+	stream.push("A", 1002., samples(1, 4));
+	stream.push("B", 1001., samples(2, 8));
+	assert_eq!(stream.get_dropped_samples(), Vec::new());
+	stream.push("A", 1003., samples(3, 4));
+	stream.push("B", 1003., samples(4, 4));
+	// the frame 1001 is written
+	stream.push("A", 1001., samples(5, 4));
+	assert_eq!(stream.get_dropped_samples(), vec![(String::from("A"), 4)]);
+	assert_eq!(stream.close(), vec![(1001, String::from("A"))]);
+
+	let frames: Vec<Frame> = read_file(file.get_path(), "B", 0., 1e10);
+	let starts: Vec<f64> = frames.iter().map(|x| x.header.get_start()).collect();
+	assert_eq!(starts, vec![1001., 1002., 1003.]);
+	assert!(!std::path::Path::new(whole_file.get_path()).exists());
+}